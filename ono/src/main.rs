@@ -2,7 +2,7 @@ use clap::Parser;
 use std::{
     fs::File,
     io::{BufReader, Read},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 #[derive(Parser, Debug)]
@@ -12,6 +12,16 @@ struct Args {
     file: PathBuf,
 }
 
+/// Prints `error` along with the line of `file` it refers to
+fn report(error: &mut onoi::Error, lines: &[&str], file: &Path) {
+    let line = lines
+        .get(error.token.position.line)
+        .expect("error line should refer to a line in src code");
+    error.with_src_line(line);
+    error.with_filename(file.to_str().unwrap());
+    eprintln!("{}\n", error);
+}
+
 fn main() {
     let args = Args::parse();
     let file = match File::open(&args.file) {
@@ -25,16 +35,18 @@ fn main() {
         panic!("could not read {:?}", args.file);
     }
 
-    match onoi::run(&code) {
+    let mut warnings = Vec::new();
+    let result = onoi::run(&code, &mut warnings);
+
+    let lines = code.split("\n").collect::<Vec<_>>();
+    for warning in warnings.iter_mut() {
+        report(warning, &lines, &args.file);
+    }
+
+    match result {
         Err(mut errors) => {
-            let lines = code.split("\n").collect::<Vec<_>>();
             for error in errors.iter_mut() {
-                let line = lines
-                    .get(error.token.position.line)
-                    .expect("error line should refer to a line in src code");
-                error.with_src_line(line);
-                error.with_filename(args.file.to_str().unwrap());
-                eprintln!("{}\n", error);
+                report(error, &lines, &args.file);
             }
 
             println!(
@@ -1,6 +1,7 @@
-use crate::types::{Token, TokenKind, Type};
+use crate::types::{Token, TokenKind, Type, Value};
 use colored::Colorize;
 use std::fmt::{self, Debug};
+use unicode_segmentation::UnicodeSegmentation;
 
 /// A static syntax error.
 /// These are caught before running the program.
@@ -27,7 +28,25 @@ pub enum SyntaxError {
     /// Unterminated block
     S010,
     /// expected block
-    S011
+    S011,
+    /// Unknown type
+    S012,
+    /// Cyclic type alias
+    S013,
+    /// Wrong number of type arguments
+    S014 { expected: usize, found: usize },
+    /// Expected pattern
+    S015,
+    /// Unknown attribute
+    S016,
+    /// Invalid attribute arguments
+    S017,
+    /// Pipe into something that is not a call
+    S018,
+    /// Arithmetic or set operator right after a pipe
+    S019,
+    /// Rest pattern before the end of a tuple pattern
+    S020,
 }
 
 impl fmt::Display for SyntaxError {
@@ -44,6 +63,15 @@ impl fmt::Display for SyntaxError {
             SyntaxError::S009 => write!(f, "S009"),
             SyntaxError::S010 => write!(f, "S010"),
             SyntaxError::S011 => write!(f, "S011"),
+            SyntaxError::S012 => write!(f, "S012"),
+            SyntaxError::S013 => write!(f, "S013"),
+            SyntaxError::S014 { .. } => write!(f, "S014"),
+            SyntaxError::S015 => write!(f, "S015"),
+            SyntaxError::S016 => write!(f, "S016"),
+            SyntaxError::S017 => write!(f, "S017"),
+            SyntaxError::S018 => write!(f, "S018"),
+            SyntaxError::S019 => write!(f, "S019"),
+            SyntaxError::S020 => write!(f, "S020"),
         }
     }
 }
@@ -74,6 +102,22 @@ pub enum TypeError {
     T007 { then: Type },
     /// branch type mismatch 
     T008 { then: Type, eelse: Type },
+    /// callee is not a function
+    T009,
+    /// wrong number of arguments
+    T010 { expected: usize, found: usize },
+    /// refutable pattern in irrefutable position
+    T011,
+    /// destructured value does not have the shape of the pattern
+    T012 { found: Type, extracted: usize },
+    /// invalid cast
+    T013 { from: Type, to: Type },
+    /// block used where a set was expected
+    T014,
+    /// element type of empty set cannot be inferred
+    T015,
+    /// name bound more than once in a pattern
+    T016,
 }
 
 impl fmt::Display for TypeError {
@@ -87,6 +131,14 @@ impl fmt::Display for TypeError {
             TypeError::T006 { .. } => write!(f, "T006"),
             TypeError::T007 { .. } => write!(f, "T007"),
             TypeError::T008 { .. } => write!(f, "T008"),
+            TypeError::T009 => write!(f, "T009"),
+            TypeError::T010 { .. } => write!(f, "T010"),
+            TypeError::T011 => write!(f, "T011"),
+            TypeError::T012 { .. } => write!(f, "T012"),
+            TypeError::T013 { .. } => write!(f, "T013"),
+            TypeError::T014 => write!(f, "T014"),
+            TypeError::T015 => write!(f, "T015"),
+            TypeError::T016 => write!(f, "T016"),
         }
     }
 }
@@ -96,12 +148,52 @@ impl fmt::Display for TypeError {
 pub enum RuntimeError {
     /// Division by zero
     R001,
+    /// Explicit panic
+    R002 { message: String },
+    /// Assertion failed
+    R003 { message: String },
+    /// Equality assertion failed
+    R004 { left: Value, right: Value },
+    /// Unreachable code was reached
+    R005,
+    /// Index out of range
+    R006 { index: f64, length: usize },
+    /// Slice out of range
+    R007 { start: f64, end: f64, length: usize },
+    /// Value does not have the type it was cast to
+    R008 { expected: Type, found: Type },
 }
 
 impl fmt::Display for RuntimeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             RuntimeError::R001 => write!(f, "{}", "R001"),
+            RuntimeError::R002 { .. } => write!(f, "R002"),
+            RuntimeError::R003 { .. } => write!(f, "R003"),
+            RuntimeError::R004 { .. } => write!(f, "R004"),
+            RuntimeError::R005 => write!(f, "R005"),
+            RuntimeError::R006 { .. } => write!(f, "R006"),
+            RuntimeError::R007 { .. } => write!(f, "R007"),
+            RuntimeError::R008 { .. } => write!(f, "R008"),
+        }
+    }
+}
+
+/// A warning about code that runs but should probably be changed.
+/// Warnings never stop the program from running.
+#[derive(Debug, PartialEq)]
+pub enum Warning {
+    /// Use of a deprecated declaration
+    W001 { message: Option<String> },
+    /// Identifier mixes scripts
+    W002,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::W001 { .. } => write!(f, "W001"),
+            Warning::W002 => write!(f, "W002"),
         }
     }
 }
@@ -111,6 +203,7 @@ pub enum ErrorKind {
     Syntax(SyntaxError),
     Type(TypeError),
     Runtime(RuntimeError),
+    Warning(Warning),
 }
 
 /// Standard ono error type
@@ -158,6 +251,15 @@ impl Error {
         }
     }
 
+    pub fn warning(warning: Warning, token: Token) -> Self {
+        Self {
+            kind: ErrorKind::Warning(warning),
+            token,
+            file: None,
+            line_src: None,
+        }
+    }
+
     pub fn with_src_line(&mut self, line_src: &str) {
         self.line_src = Some(line_src.to_string())
     }
@@ -176,7 +278,7 @@ impl Error {
                         .take(row_str.len() + self.token.position.column)
                         .collect::<String>();
                     let arrows = std::iter::repeat("^")
-                        .take(self.token.lexeme.len())
+                        .take(self.token.lexeme.graphemes(true).count())
                         .collect::<String>();
 
                     format!("{}{}", spaces, arrows.red().bold())
@@ -198,11 +300,11 @@ impl Error {
 
     fn format_message(&self) -> String {
         let identifier = match &self.kind {
-            ErrorKind::Syntax(kind) => format!("[{}] error", kind),
-            ErrorKind::Type(kind) => format!("[{}] type error", kind),
-            ErrorKind::Runtime(kind) => format!("[{}] runtime error", kind),
-        }
-        .bright_red();
+            ErrorKind::Syntax(kind) => format!("[{}] error", kind).bright_red(),
+            ErrorKind::Type(kind) => format!("[{}] type error", kind).bright_red(),
+            ErrorKind::Runtime(kind) => format!("[{}] runtime error", kind).bright_red(),
+            ErrorKind::Warning(kind) => format!("[{}] warning", kind).bright_yellow(),
+        };
 
         let message = match &self.kind {
             ErrorKind::Syntax(errno) => match errno {
@@ -223,6 +325,27 @@ impl Error {
                 SyntaxError::S009 => format!("cannot assign to left hand side"),
                 SyntaxError::S010 => format!("unterminated block starting here"),
                 SyntaxError::S011 => format!("expected block after this"),
+                SyntaxError::S012 => format!("unknown type '{}'", self.token.lexeme),
+                SyntaxError::S013 => {
+                    format!("type alias '{}' refers to itself", self.token.lexeme)
+                }
+                SyntaxError::S014 { expected, found } => format!(
+                    "'{}' expects {} type arguments but found {}",
+                    self.token.lexeme, expected, found
+                ),
+                SyntaxError::S015 => format!("expected pattern after '{}'", self.token.lexeme),
+                SyntaxError::S016 => format!("unknown attribute '@{}'", self.token.lexeme),
+                SyntaxError::S017 => {
+                    format!("invalid arguments for attribute '@{}'", self.token.lexeme)
+                }
+                SyntaxError::S018 => format!("expected call after '{}'", self.token.lexeme),
+                SyntaxError::S019 => format!(
+                    "cannot use '{}' right after a pipe, wrap the pipe in parentheses",
+                    self.token.lexeme
+                ),
+                SyntaxError::S020 => {
+                    format!("'...' can only be the last element of a tuple pattern")
+                }
             },
             ErrorKind::Type(errno) => match errno {
                 TypeError::T001 { left, right } => format!(
@@ -268,9 +391,71 @@ impl Error {
                     format!("{}", then).cyan(),
                     format!("{}", eelse).cyan()
                 ),
+                TypeError::T009 => format!("'{}' is not a function", self.token.lexeme),
+                TypeError::T010 { expected, found } => format!(
+                    "'{}' takes {} arguments but {} were given",
+                    self.token.lexeme, expected, found
+                ),
+                TypeError::T011 => format!(
+                    "pattern '{}' might not match, so it cannot be used in a let binding",
+                    self.token.lexeme
+                ),
+                TypeError::T012 { found, extracted } => format!(
+                    "cannot destructure {} into {} elements",
+                    format!("{}", found).cyan(),
+                    extracted
+                ),
+                TypeError::T013 { from, to } => format!(
+                    "cannot cast {} as {}",
+                    format!("{}", from).cyan(),
+                    format!("{}", to).cyan()
+                ),
+                TypeError::T014 => {
+                    format!("this is a block, write '{{a,}}' for a set of one and '{{,}}' for none")
+                }
+                TypeError::T015 => format!(
+                    "cannot infer the element type of the empty set assigned to '{}', add a type annotation",
+                    self.token.lexeme
+                ),
+                TypeError::T016 => format!(
+                    "'{}' is bound more than once in the same pattern",
+                    self.token.lexeme
+                ),
             },
             ErrorKind::Runtime(errno) => match errno {
                 RuntimeError::R001 => format!("division by zero here"),
+                RuntimeError::R002 { message } => format!("panicked with '{}'", message),
+                RuntimeError::R003 { message } => format!("assertion failed: {}", message),
+                RuntimeError::R004 { left, right } => format!(
+                    "assertion failed: {} does not equal {}",
+                    format!("{}", left).cyan(),
+                    format!("{}", right).cyan()
+                ),
+                RuntimeError::R005 => format!("entered unreachable code"),
+                RuntimeError::R006 { index, length } => {
+                    format!("index {} is out of range for length {}", index, length)
+                }
+                RuntimeError::R007 { start, end, length } => format!(
+                    "slice {}..{} is out of range for length {}",
+                    start, end, length
+                ),
+                RuntimeError::R008 { expected, found } => format!(
+                    "cannot cast {} as {}",
+                    format!("{}", found).cyan(),
+                    format!("{}", expected).cyan()
+                ),
+            },
+            ErrorKind::Warning(warning) => match warning {
+                Warning::W001 { message: None } => {
+                    format!("'{}' is deprecated", self.token.lexeme)
+                }
+                Warning::W001 {
+                    message: Some(message),
+                } => format!("'{}' is deprecated: {}", self.token.lexeme, message),
+                Warning::W002 => format!(
+                    "'{}' mixes scripts, so it is easily confused with other names",
+                    self.token.lexeme
+                ),
             },
         };
 
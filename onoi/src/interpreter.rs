@@ -1,7 +1,9 @@
+use unicode_segmentation::UnicodeSegmentation;
+
 use crate::{
     environment::Environment,
     error::{language_error, Error, RuntimeError},
-    types::{Expr, Stmt, TokenKind, Value},
+    types::{Builtin, Expr, Pattern, Stmt, Token, TokenKind, Value},
 };
 
 pub struct Interpreter {
@@ -27,7 +29,9 @@ impl Interpreter {
                     }
                 }
                 Err(error) => {
+                    // Runtime errors crash the program, so nothing after it is run
                     errors.push(error);
+                    break;
                 }
             }
         }
@@ -41,16 +45,34 @@ impl Interpreter {
 
     pub fn visit_statement(&mut self, statement: &Stmt) -> Result<Value, Error> {
         match statement {
-            Stmt::Expression { expr } => Ok(self.visit_expression(expr)?),
+            Stmt::Expression { expr } | Stmt::BlockLike { expr } => {
+                Ok(self.visit_expression(expr)?)
+            }
             Stmt::Let {
-                name,
+                attributes: _,
+                pattern,
                 ttype: _,
                 initializer,
             } => {
                 let value = self.visit_expression(initializer)?;
-                self.scope.define(&name.lexeme, value);
+                self.bind(pattern, value);
                 Ok(Value::Tuple(vec![]))
             }
+            Stmt::Type { .. } => Ok(Value::Tuple(vec![])),
+        }
+    }
+
+    /// Defines the names bound by matching `pattern` against `value`
+    fn bind(&mut self, pattern: &Pattern, value: Value) {
+        match (pattern, value) {
+            (Pattern::Binding { name }, value) => self.scope.define(&name.lexeme, value),
+            (Pattern::Wildcard { .. }, _) => {}
+            (Pattern::Tuple { elements, .. }, Value::Tuple(values)) => {
+                for (element, value) in elements.iter().zip(values) {
+                    self.bind(element, value);
+                }
+            }
+            _ => language_error("refutable pattern that was not type checked"),
         }
     }
 
@@ -64,6 +86,12 @@ impl Interpreter {
                     .map(|expr| self.visit_expression(expr))
                     .collect::<Result<Vec<Value>, Error>>()?,
             )),
+            Expr::Set { elements, .. } => Ok(Value::set(
+                elements
+                    .iter()
+                    .map(|element| self.visit_expression(element))
+                    .collect::<Result<Vec<Value>, Error>>()?,
+            )),
             Expr::Logical {
                 operator,
                 left,
@@ -91,6 +119,29 @@ impl Interpreter {
                     _ => language_error(&format!("Unknown unary operator")),
                 }
             }
+            Expr::Cast {
+                expr,
+                keyword,
+                ttype,
+                from,
+            } => {
+                let value = self.visit_expression(expr)?;
+                let found = value.type_of();
+                let from = match from.get() {
+                    Some(from) => from,
+                    None => language_error("cast that was not type checked"),
+                };
+                match value.cast(from, ttype) {
+                    Some(value) => Ok(value),
+                    None => Err(Error::runtime_error(
+                        RuntimeError::R008 {
+                            expected: ttype.clone(),
+                            found,
+                        },
+                        keyword.clone(),
+                    )),
+                }
+            }
             Expr::Binary {
                 operator,
                 left,
@@ -130,7 +181,38 @@ impl Interpreter {
                     }
                 }
 
+                if let (Value::Set(l), Value::Set(r)) = (&left, &right) {
+                    let is_subset = l.iter().all(|element| r.contains(element));
+                    let is_superset = r.iter().all(|element| l.contains(element));
+                    match operator.kind {
+                        TokenKind::PIPE => {
+                            return Ok(Value::set(l.iter().chain(r).cloned().collect()))
+                        }
+                        TokenKind::AMPERSAND => {
+                            return Ok(Value::Set(
+                                l.iter().filter(|e| r.contains(e)).cloned().collect(),
+                            ))
+                        }
+                        TokenKind::MINUS => {
+                            return Ok(Value::Set(
+                                l.iter().filter(|e| !r.contains(e)).cloned().collect(),
+                            ))
+                        }
+                        TokenKind::LESS => return Ok(Value::Bool(is_subset && l.len() < r.len())),
+                        TokenKind::LESSEQUAL => return Ok(Value::Bool(is_subset)),
+                        TokenKind::GREATER => {
+                            return Ok(Value::Bool(is_superset && l.len() > r.len()))
+                        }
+                        TokenKind::GREATEREQUAL => return Ok(Value::Bool(is_superset)),
+                        _ => {}
+                    }
+                }
+
                 match operator.kind {
+                    TokenKind::IN => match right {
+                        Value::Set(elements) => return Ok(Value::Bool(elements.contains(&left))),
+                        _ => language_error("membership test on a non-set value"),
+                    },
                     TokenKind::EQUALEQUAL => return Ok(Value::Bool(left == right)),
                     TokenKind::BANGEQUAL => return Ok(Value::Bool(left != right)),
                     _ => language_error(&format!("unknown binary operator '{}'", operator.lexeme)),
@@ -143,6 +225,81 @@ impl Interpreter {
                     language_error("undefined variable that was not type checked");
                 }
             }
+            Expr::Call {
+                callee, arguments, ..
+            } => {
+                let name = match callee.as_ref() {
+                    Expr::Variable { name } => name,
+                    _ => language_error("call to non-function that was not type checked"),
+                };
+
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.visit_expression(argument))
+                    .collect::<Result<Vec<Value>, Error>>()?;
+                match Builtin::from_name(&name.lexeme) {
+                    Some(builtin) => self.call_builtin(builtin, name, arguments),
+                    None => language_error(&format!("unknown function '{}'", name.lexeme)),
+                }
+            }
+            Expr::Pipe { call, .. } => self.visit_expression(call),
+            Expr::Index {
+                expr,
+                bracket,
+                index,
+            } => {
+                let text = self.visit_text(expr)?;
+                let index = self.visit_number(index)?;
+                let graphemes = text.graphemes(true).collect::<Vec<&str>>();
+                if index.fract() != 0.0 || index < 0.0 || index >= graphemes.len() as f64 {
+                    return Err(Error::runtime_error(
+                        RuntimeError::R006 {
+                            index,
+                            length: graphemes.len(),
+                        },
+                        bracket.clone(),
+                    ));
+                }
+
+                Ok(Value::Text(graphemes[index as usize].to_string()))
+            }
+            Expr::Slice {
+                expr,
+                bracket,
+                start,
+                end,
+            } => {
+                let text = self.visit_text(expr)?;
+                let graphemes = text.graphemes(true).collect::<Vec<&str>>();
+                let start = match start {
+                    Some(start) => self.visit_number(start)?,
+                    None => 0.0,
+                };
+                let end = match end {
+                    Some(end) => self.visit_number(end)?,
+                    None => graphemes.len() as f64,
+                };
+
+                if start.fract() != 0.0
+                    || end.fract() != 0.0
+                    || start < 0.0
+                    || start > end
+                    || end > graphemes.len() as f64
+                {
+                    return Err(Error::runtime_error(
+                        RuntimeError::R007 {
+                            start,
+                            end,
+                            length: graphemes.len(),
+                        },
+                        bracket.clone(),
+                    ));
+                }
+
+                Ok(Value::Text(
+                    graphemes[start as usize..end as usize].concat(),
+                ))
+            }
             Expr::Assign { name, expr } => {
                 let value = self.visit_expression(expr)?;
                 if let Err(_) = self.scope.assign(&name.lexeme, value.clone()) {
@@ -157,6 +314,7 @@ impl Interpreter {
             Expr::Block {
                 statements,
                 finally,
+                ..
             } => {
                 self.scope = self.scope.new_nested();
                 for stmt in statements {
@@ -195,13 +353,77 @@ impl Interpreter {
             }
         }
     }
+
+    fn visit_text(&mut self, expr: &Expr) -> Result<String, Error> {
+        match self.visit_expression(expr)? {
+            Value::Text(text) => Ok(text),
+            _ => language_error("expected string value that was not type checked"),
+        }
+    }
+
+    fn visit_number(&mut self, expr: &Expr) -> Result<f64, Error> {
+        match self.visit_expression(expr)? {
+            Value::Number(number) => Ok(number),
+            _ => language_error("expected number value that was not type checked"),
+        }
+    }
+
+    fn call_builtin(
+        &mut self,
+        builtin: Builtin,
+        call_site: &Token,
+        arguments: Vec<Value>,
+    ) -> Result<Value, Error> {
+        let mut arguments = arguments.into_iter();
+        let mut next_argument = || match arguments.next() {
+            Some(argument) => argument,
+            None => language_error("builtin called with too few arguments"),
+        };
+
+        match builtin {
+            Builtin::Panic => Err(Error::runtime_error(
+                RuntimeError::R002 {
+                    message: next_argument().to_string(),
+                },
+                call_site.clone(),
+            )),
+            Builtin::Assert => {
+                let condition = next_argument();
+                let message = next_argument().to_string();
+                if condition.is_truthy() {
+                    Ok(Value::Tuple(Vec::new()))
+                } else {
+                    Err(Error::runtime_error(
+                        RuntimeError::R003 { message },
+                        call_site.clone(),
+                    ))
+                }
+            }
+            Builtin::AssertEq => {
+                let left = next_argument();
+                let right = next_argument();
+                if left == right {
+                    Ok(Value::Tuple(Vec::new()))
+                } else {
+                    Err(Error::runtime_error(
+                        RuntimeError::R004 { left, right },
+                        call_site.clone(),
+                    ))
+                }
+            }
+            Builtin::Unreachable => {
+                Err(Error::runtime_error(RuntimeError::R005, call_site.clone()))
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::types::{Expr, Token, TokenKind, Value};
+    use crate::types::{Expr, Token, TokenKind, Type, Value};
     use pretty_assertions::assert_eq;
+    use std::cell::OnceCell;
     use Expr::*;
     use TokenKind::*;
 
@@ -328,4 +550,169 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn errors_on_panic() -> Result<(), Error> {
+        let expr = Call {
+            callee: Box::new(Variable {
+                name: Token::new(IDENTIFIER("panic".to_string()), 0, 0, "panic"),
+            }),
+            paren: Token::new(LEFTPAREN, 0, 5, "("),
+            arguments: vec![Literal {
+                value: Token::new(STRING("oh no".to_string()), 0, 6, "\"oh no\""),
+            }],
+        };
+
+        let result = Interpreter::new().visit_expression(&expr);
+        assert_eq!(
+            result,
+            Err(Error::runtime_error(
+                RuntimeError::R002 {
+                    message: "oh no".to_string()
+                },
+                Token::new(IDENTIFIER("panic".to_string()), 0, 0, "panic")
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn set_union() -> Result<(), Error> {
+        let expr = Binary {
+            left: Box::new(Set {
+                brace: Token::new(LEFTBRACE, 0, 0, "{"),
+                elements: vec![
+                    Literal {
+                        value: Token::new(NUMBER(1.0), 0, 1, "1"),
+                    },
+                    Literal {
+                        value: Token::new(NUMBER(2.0), 0, 4, "2"),
+                    },
+                ],
+            }),
+            operator: Token::new(PIPE, 0, 7, "|"),
+            right: Box::new(Set {
+                brace: Token::new(LEFTBRACE, 0, 9, "{"),
+                elements: vec![
+                    Literal {
+                        value: Token::new(NUMBER(2.0), 0, 10, "2"),
+                    },
+                    Literal {
+                        value: Token::new(NUMBER(3.0), 0, 13, "3"),
+                    },
+                ],
+            }),
+        };
+
+        let result = Interpreter::new().visit_expression(&expr)?;
+        assert_eq!(
+            result,
+            Value::Set(vec![
+                Value::Number(3.0),
+                Value::Number(2.0),
+                Value::Number(1.0)
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn string_slicing_by_grapheme() -> Result<(), Error> {
+        let text = Box::new(Literal {
+            value: Token::new(STRING("🇩🇰é!".to_string()), 0, 0, "\"🇩🇰é!\""),
+        });
+        let expr = Slice {
+            expr: text.clone(),
+            bracket: Token::new(LEFTBRACKET, 0, 5, "["),
+            start: Some(Box::new(Literal {
+                value: Token::new(NUMBER(0.0), 0, 6, "0"),
+            })),
+            end: Some(Box::new(Literal {
+                value: Token::new(NUMBER(2.0), 0, 9, "2"),
+            })),
+        };
+
+        let result = Interpreter::new().visit_expression(&expr)?;
+        assert_eq!(result, Value::Text("🇩🇰é".to_string()));
+
+        let expr_open_end = Slice {
+            expr: text,
+            bracket: Token::new(LEFTBRACKET, 0, 5, "["),
+            start: Some(Box::new(Literal {
+                value: Token::new(NUMBER(2.0), 0, 6, "2"),
+            })),
+            end: None,
+        };
+        assert_eq!(
+            Interpreter::new().visit_expression(&expr_open_end),
+            Ok(Value::Text("!".to_string()))
+        );
+
+        let expr_out_of_range = Slice {
+            expr: Box::new(Literal {
+                value: Token::new(STRING("ab".to_string()), 0, 0, "\"ab\""),
+            }),
+            bracket: Token::new(LEFTBRACKET, 0, 4, "["),
+            start: None,
+            end: Some(Box::new(Literal {
+                value: Token::new(NUMBER(3.0), 0, 7, "3"),
+            })),
+        };
+        assert_eq!(
+            Interpreter::new().visit_expression(&expr_out_of_range),
+            Err(Error::runtime_error(
+                RuntimeError::R007 {
+                    start: 0.0,
+                    end: 3.0,
+                    length: 2
+                },
+                Token::new(LEFTBRACKET, 0, 4, "[")
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn errors_on_failed_cast() -> Result<(), Error> {
+        // A bool coming out of `any` is checked, not converted to a number
+        let keyword = Token::new(AS, 0, 5, "as");
+        let expr = Cast {
+            expr: Box::new(Literal {
+                value: Token::new(TRUE, 0, 0, "true"),
+            }),
+            keyword: keyword.clone(),
+            ttype: Type::Number,
+            from: OnceCell::from(Type::Any),
+        };
+
+        let result = Interpreter::new().visit_expression(&expr);
+        assert_eq!(
+            result,
+            Err(Error::runtime_error(
+                RuntimeError::R008 {
+                    expected: Type::Number,
+                    found: Type::Bool
+                },
+                keyword
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn converts_number_to_text() -> Result<(), Error> {
+        let keyword = Token::new(AS, 0, 4, "as");
+        let expr = Cast {
+            expr: Box::new(Literal {
+                value: Token::new(NUMBER(1.5), 0, 0, "1.5"),
+            }),
+            keyword,
+            ttype: Type::Text,
+            from: OnceCell::from(Type::Number),
+        };
+
+        let result = Interpreter::new().visit_expression(&expr)?;
+        assert_eq!(result, Value::Text("1.5".to_string()));
+        Ok(())
+    }
 }
@@ -1,19 +1,36 @@
 use crate::{
-    error::{Error, SyntaxError},
+    error::{Error, SyntaxError, Warning},
     types::{Token, TokenKind},
 };
+use unicode_normalization::UnicodeNormalization;
+use unicode_security::MixedScript;
 use unicode_segmentation::UnicodeSegmentation;
+use unicode_xid::UnicodeXID;
+
+// ONO LEXICAL GRAMMAR
+// Source is split into extended grapheme clusters, which is what a column counts.
+// identifier  -> start continue* ; NFC normalized, so equal looking names are equal
+// start       -> grapheme starting with an XID_Start char or "_", followed by XID_Continue chars ;
+// continue    -> grapheme of only XID_Continue chars ;
+// number      -> DIGIT+ ( "." DIGIT+ )? ;
+// string      -> "\"" ( any grapheme except "\"" )* "\"" ;
+// comment     -> "#" ( any grapheme except "\n" )* "\n" ;
+// DIGIT       -> "0" ... "9" ;
 
 fn is_digit(c: &str) -> bool {
     matches!(c, "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9")
 }
 
 fn is_alpha(c: &str) -> bool {
-    (c >= "a" && c <= "z") || (c >= "A" && c <= "Z") || c == "_"
+    let mut chars = c.chars();
+    match chars.next() {
+        Some(first) if first.is_xid_start() || first == '_' => chars.all(|c| c.is_xid_continue()),
+        _ => false,
+    }
 }
 
 fn is_alpha_numeric(c: &str) -> bool {
-    is_digit(c) || is_alpha(c)
+    !c.is_empty() && c.chars().all(|c| c.is_xid_continue())
 }
 
 pub struct Lexer {
@@ -30,6 +47,8 @@ pub struct Lexer {
     /// column number of current in src.
     /// Note that this is not the column start of the lexeme but the end
     column_end: usize,
+    /// Warnings found while tokenizing, these do not fail the tokenization
+    pub warnings: Vec<Error>,
 }
 
 impl Lexer {
@@ -41,6 +60,7 @@ impl Lexer {
             current: 0,
             line: 0,
             column_end: 0,
+            warnings: Vec::new(),
         }
     }
 
@@ -79,11 +99,16 @@ impl Lexer {
     }
 
     fn add_token(&mut self, kind: TokenKind) {
+        let lexeme = self.graphemes[self.start..self.current].join("");
+        self.add_token_with_lexeme(kind, &lexeme);
+    }
+
+    fn add_token_with_lexeme(&mut self, kind: TokenKind, lexeme: &str) {
         self.tokens.push(Token::new(
             kind,
             self.line,
             self.column_end - (self.current - self.start),
-            &self.graphemes[self.start..self.current].join(""),
+            lexeme,
         ));
     }
 
@@ -146,7 +171,23 @@ impl Lexer {
             "+" => self.add_token(TokenKind::PLUS),
             "*" => self.add_token(TokenKind::STAR),
             "/" => self.add_token(TokenKind::SLASH),
+            "|" => {
+                if self.is_next(">") {
+                    self.add_token(TokenKind::PIPEGREATER);
+                } else {
+                    self.add_token(TokenKind::PIPE);
+                }
+            }
+            "&" => self.add_token(TokenKind::AMPERSAND),
             "," => self.add_token(TokenKind::COMMA),
+            "@" => self.add_token(TokenKind::AT),
+            "." if self.is_next(".") => {
+                if self.is_next(".") {
+                    self.add_token(TokenKind::DOTDOTDOT);
+                } else {
+                    self.add_token(TokenKind::DOTDOT);
+                }
+            }
             ":" => self.add_token(TokenKind::COLON),
             ";" => self.add_token(TokenKind::SEMICOLON),
             "!" => {
@@ -252,23 +293,39 @@ impl Lexer {
             self.advance();
         }
 
-        let lexeme = self.graphemes[(self.start)..(self.current)].join("");
+        let lexeme = self.graphemes[(self.start)..(self.current)]
+            .join("")
+            .nfc()
+            .collect::<String>();
         let token = match lexeme.as_str() {
             "and" => TokenKind::AND,
             "false" => TokenKind::FALSE,
             "or" => TokenKind::OR,
             "true" => TokenKind::TRUE,
             "let" => TokenKind::LET,
+            "type" => TokenKind::TYPE,
             "string" => TokenKind::STRINGKW,
             "number" => TokenKind::NUMBERKW,
             "bool" => TokenKind::BOOL,
+            "any" => TokenKind::ANY,
+            "as" => TokenKind::AS,
             "if" => TokenKind::IF,
             "else" => TokenKind::ELSE,
             "while" => TokenKind::WHILE,
+            "in" => TokenKind::IN,
             identifier => TokenKind::IDENTIFIER(identifier.to_string()),
         };
 
-        self.add_token(token)
+        self.add_token_with_lexeme(token, &lexeme);
+        // Only the first use of a name is reported, the rest would be noise
+        let reported = self
+            .warnings
+            .iter()
+            .any(|warning| warning.token.lexeme == lexeme);
+        if !lexeme.is_single_script() && !reported {
+            let token = self.tokens.last().unwrap().clone();
+            self.warnings.push(Error::warning(Warning::W002, token));
+        }
     }
 }
 
@@ -362,4 +419,37 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn normalizes_unicode_identifiers() -> Result<(), Vec<Error>> {
+        // "café" written with a precomposed 'é' and with 'e' followed by a combining accent
+        let src = "caf\u{e9} cafe\u{301} 名前";
+        let target = vec![
+            Token::new(IDENTIFIER("caf\u{e9}".to_string()), 0, 0, "caf\u{e9}"),
+            Token::new(IDENTIFIER("caf\u{e9}".to_string()), 0, 5, "caf\u{e9}"),
+            Token::new(IDENTIFIER("名前".to_string()), 0, 10, "名前"),
+            Token::new(EOF, 1, 0, "\n"),
+        ];
+
+        let mut lexer = Lexer::new();
+        assert_eq!(lexer.tokenize(src)?, target);
+        assert_eq!(lexer.warnings, vec![]);
+        Ok(())
+    }
+
+    #[test]
+    fn warns_on_mixed_script_identifier() -> Result<(), Vec<Error>> {
+        // The second 'a' is cyrillic
+        let src = "pa\u{430}";
+        let mut lexer = Lexer::new();
+        lexer.tokenize(src)?;
+        assert_eq!(
+            lexer.warnings,
+            vec![Error::warning(
+                Warning::W002,
+                Token::new(IDENTIFIER("pa\u{430}".to_string()), 0, 0, "pa\u{430}")
+            )]
+        );
+        Ok(())
+    }
 }
@@ -6,18 +6,25 @@ mod parser;
 mod typechecker;
 mod types;
 
-use error::Error;
+pub use error::Error;
 use interpreter::Interpreter;
 use lexer::Lexer;
 use parser::Parser;
 use typechecker::Typechecker;
 use types::Value;
 
-/// Runs a ono program
-pub fn run(program: &str) -> Result<Value, Vec<Error>> {
-    let tokens = Lexer::new().tokenize(program)?;
+/// Runs a ono program, collecting any warnings into `warnings`
+pub fn run(program: &str, warnings: &mut Vec<Error>) -> Result<Value, Vec<Error>> {
+    let mut lexer = Lexer::new();
+    let tokens = lexer.tokenize(program);
+    warnings.append(&mut lexer.warnings);
+    let tokens = tokens?;
     let statements = Parser::new().parse(tokens)?;
 
-    Typechecker::new().check(&statements)?;
+    let mut typechecker = Typechecker::new();
+    let checked = typechecker.check(&statements);
+    warnings.append(&mut typechecker.warnings);
+    checked?;
+
     Ok(Interpreter::new().interpret(&statements)?)
 }
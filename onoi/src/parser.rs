@@ -1,37 +1,62 @@
+use std::cell::OnceCell;
+
+use crate::environment::Environment;
 use crate::error::{Error, SyntaxError};
-use crate::types::{Expr, Stmt, Token, TokenKind, Type};
+use crate::types::{Attribute, Expr, Pattern, Stmt, Token, TokenKind, Type};
 
 /// ONO GRAMMAR
 /// program     -> statement* EOF;
 
-/// statement   -> letStmt | exprStmt ;
-/// letStmt     -> "let" IDENTIFIER (":" type)? "=" expression ";" ;
-/// exprStmt    -> expression ";" ;
+/// statement   -> typeStmt | attribute* letStmt | exprStmt ;
+/// attribute   -> "@" IDENTIFIER ( "(" STRING ")" )? ;
+/// typeStmt    -> "type" IDENTIFIER ( "<" IDENTIFIER ( "," IDENTIFIER )* ">" )? "=" type ";" ;
+/// letStmt     -> "let" pattern (":" type)? "=" expression ";" ;
+/// exprStmt    -> expression ";" | blockLike ";"? ;
+/// blockLike   -> if | while | block ;
 
-/// expression  -> assignment | block | if | while ;
+/// expression  -> assignment | if | while ;
 /// while       -> "while" logic_or block ;
 /// if          -> "if" logic_or block ( "else" ( block | if ) )? ;
-/// block       -> "{" ( statement* assignment )? "}" ;
+/// block       -> "{" statement* expression? "}" | set ;
+/// set         -> "{" "," "}" | "{" assignment "," ( assignment ( "," assignment )* ","? )? "}" ;
 /// assignment  -> IDENTIFIER "=" assignment | logic_or ;
 /// logic_or    -> logic_and ( "or" logic_and )* ;
 /// logic_and   -> equality ( "and" equality )* ;
 /// equality    -> comparison ( ("!=" | "==") comparison )* ;
-/// comparison  -> term ( (">" | ">=" | "<" | "<=") term )* ;
-/// term        -> factor ( ("-" | "+") factor )* ;
-/// factor      -> unary ( ("/" | "*") unary )* ;
-/// unary       -> ("!" | "-") unary | primary ;
-/// primary     -> NUMBER | STRING | IDENTIFIER | "true" | "false" | "null" | tuple ;
+/// comparison  -> pipeline ( (">" | ">=" | "<" | "<=" | "in") pipeline )* ;
+/// pipeline    -> term ( "|>" call )* ;
+/// term        -> factor ( ("-" | "+" | "|" | "&") factor )* ;
+/// factor      -> cast ( ("/" | "*") cast )* ;
+/// cast        -> unary ( "as" type )* ;
+/// unary       -> ("!" | "-") unary | call ;
+/// call        -> primary ( "(" arguments? ")" | "[" index "]" )* ;
+/// arguments   -> expression ( "," expression )* ;
+/// index       -> expression | expression? ".." expression? ;
+/// primary     -> NUMBER | STRING | IDENTIFIER | "true" | "false" | "null" | tuple | block ;
 /// tuple       -> "(" expression ( "," expression )* ")" ;
 
-/// type        -> list_type | tuple_type | simple_type "?"? ;
+/// pattern     -> IDENTIFIER | "_" | NUMBER | STRING | "true" | "false" | tuple_pat ;
+/// tuple_pat   -> "(" ( pattern ( "," pattern )* ( "," "..." )? | "..." )? ")" ;
+
+/// type        -> list_type | tuple_type | set_type | simple_type "?"? ;
 /// list_type   -> "[" type ( "," type )* "]" ;
 /// tuple_type  -> "(" type ("," type )* ")" ;
-/// simple_type -> "string" | "number" | "bool" ;
+/// set_type    -> "{" type "}" ;
+/// simple_type -> "string" | "number" | "bool" | "any" | alias_type ;
+/// alias_type  -> IDENTIFIER ( "<" type ( "," type )* ">" )? ;
 
 /// Parses a Vec<Token> into an expression
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    /// Type aliases in scope along with their type parameters
+    aliases: Environment<(Vec<String>, Type)>,
+    /// Name of the alias currently being declared, used to catch cycles
+    declaring: Option<String>,
+    /// Type parameters of the alias currently being declared
+    params: Vec<String>,
+    /// An operand that was already parsed, taken by the next call to `primary`
+    operand: Option<Expr>,
 }
 
 impl Parser {
@@ -39,6 +64,10 @@ impl Parser {
         Self {
             tokens: Vec::new(),
             current: 0,
+            aliases: Environment::new(),
+            declaring: None,
+            params: Vec::new(),
+            operand: None,
         }
     }
 
@@ -67,14 +96,30 @@ impl Parser {
     }
 
     fn statement(&mut self) -> Result<Stmt, Error> {
+        if self.consume(&TokenKind::TYPE).is_some() {
+            return self.type_declaration();
+        }
+
+        let mut attributes = Vec::new();
+        while self.consume(&TokenKind::AT).is_some() {
+            attributes.push(self.attribute()?);
+        }
+
         if self.consume(&TokenKind::LET).is_some() {
-            return self.let_declaration();
+            return self.let_declaration(attributes);
+        }
+
+        if !attributes.is_empty() {
+            return Err(Error::syntax_error(
+                SyntaxError::S005(TokenKind::LET),
+                self.previous().clone(),
+            ));
         }
 
         self.expression_statement()
     }
 
-    fn let_declaration(&mut self) -> Result<Stmt, Error> {
+    fn type_declaration(&mut self) -> Result<Stmt, Error> {
         let name = match self.consume(&TokenKind::IDENTIFIER("".to_string())) {
             Some(token) => token.clone(),
             None => {
@@ -85,6 +130,101 @@ impl Parser {
             }
         };
 
+        let mut params = Vec::new();
+        if self.consume(&TokenKind::LESS).is_some() {
+            loop {
+                match self.consume(&TokenKind::IDENTIFIER("".to_string())) {
+                    Some(token) => params.push(token.lexeme.clone()),
+                    None => {
+                        return Err(Error::syntax_error(
+                            SyntaxError::S007,
+                            self.previous().clone(),
+                        ))
+                    }
+                }
+
+                if self.consume(&TokenKind::COMMA).is_none() {
+                    break;
+                }
+            }
+
+            if self.consume(&TokenKind::GREATER).is_none() {
+                return Err(Error::syntax_error(
+                    SyntaxError::S005(TokenKind::GREATER),
+                    self.previous().clone(),
+                ));
+            }
+        }
+
+        if self.consume(&TokenKind::EQUAL).is_none() {
+            return Err(Error::syntax_error(
+                SyntaxError::S005(TokenKind::EQUAL),
+                self.previous().clone(),
+            ));
+        }
+
+        self.declaring = Some(name.lexeme.clone());
+        self.params = params;
+        let ttype = self.ttype();
+        let params = std::mem::take(&mut self.params);
+        self.declaring = None;
+        let ttype = ttype?;
+
+        if self.consume(&TokenKind::SEMICOLON).is_none() {
+            return Err(Error::syntax_error(
+                SyntaxError::S005(TokenKind::SEMICOLON),
+                self.previous().clone(),
+            ));
+        }
+
+        self.aliases.define(&name.lexeme, (params, ttype.clone()));
+        Ok(Stmt::Type { name, ttype })
+    }
+
+    fn attribute(&mut self) -> Result<Attribute, Error> {
+        let name = match self.consume(&TokenKind::IDENTIFIER("".to_string())) {
+            Some(token) => token.clone(),
+            None => {
+                return Err(Error::syntax_error(
+                    SyntaxError::S007,
+                    self.previous().clone(),
+                ))
+            }
+        };
+
+        match name.lexeme.as_str() {
+            "deprecated" => {
+                let message = self.attribute_message(&name)?;
+                Ok(Attribute::Deprecated { name, message })
+            }
+            _ => Err(Error::syntax_error(SyntaxError::S016, name)),
+        }
+    }
+
+    /// Parses the optional `("message")` after an attribute
+    fn attribute_message(&mut self, name: &Token) -> Result<Option<String>, Error> {
+        if self.consume(&TokenKind::LEFTPAREN).is_none() {
+            return Ok(None);
+        }
+
+        let message = match self
+            .consume(&TokenKind::STRING("".to_string()))
+            .map(|token| token.kind.clone())
+        {
+            Some(TokenKind::STRING(text)) => text,
+            _ => return Err(Error::syntax_error(SyntaxError::S017, name.clone())),
+        };
+
+        if self.consume(&TokenKind::RIGHTPAREN).is_none() {
+            return Err(Error::syntax_error(SyntaxError::S017, name.clone()));
+        }
+
+        Ok(Some(message))
+    }
+
+    fn let_declaration(&mut self, attributes: Vec<Attribute>) -> Result<Stmt, Error> {
+        let pattern = self.pattern()?;
+
         let ttype = if self.consume(&TokenKind::COLON).is_none() {
             None
         } else {
@@ -92,13 +232,17 @@ impl Parser {
         };
 
         if self.consume(&TokenKind::EQUAL).is_none() {
-            return Err(Error::syntax_error(SyntaxError::S008, name));
+            return Err(Error::syntax_error(
+                SyntaxError::S008,
+                pattern.token().clone(),
+            ));
         }
 
         let initializer = self.expression()?;
         match self.consume(&TokenKind::SEMICOLON) {
             Some(_) => Ok(Stmt::Let {
-                name,
+                attributes,
+                pattern,
                 ttype,
                 initializer,
             }),
@@ -109,23 +253,115 @@ impl Parser {
         }
     }
 
+    fn pattern(&mut self) -> Result<Pattern, Error> {
+        if let Some(token) = self.consume(&TokenKind::IDENTIFIER("".to_string())) {
+            let token = token.clone();
+            return Ok(if token.lexeme == "_" {
+                Pattern::Wildcard { token }
+            } else {
+                Pattern::Binding { name: token }
+            });
+        }
+
+        if self.is_token_of_kind(&[
+            TokenKind::FALSE,
+            TokenKind::TRUE,
+            TokenKind::NUMBER(1.0),
+            TokenKind::STRING("".to_string()),
+        ]) {
+            return Ok(Pattern::Literal {
+                value: self.previous().clone(),
+            });
+        }
+
+        if self.consume(&TokenKind::LEFTPAREN).is_some() {
+            return self.tuple_pattern();
+        }
+
+        Err(Error::syntax_error(
+            SyntaxError::S015,
+            self.previous().clone(),
+        ))
+    }
+
+    fn tuple_pattern(&mut self) -> Result<Pattern, Error> {
+        let paren = self.previous().clone();
+        let mut elements = Vec::new();
+        let mut rest = false;
+
+        while !self.check(&TokenKind::RIGHTPAREN) {
+            if self.consume(&TokenKind::DOTDOTDOT).is_some() {
+                if !self.check(&TokenKind::RIGHTPAREN) {
+                    return Err(Error::syntax_error(
+                        SyntaxError::S020,
+                        self.previous().clone(),
+                    ));
+                }
+                rest = true;
+                break;
+            }
+
+            elements.push(self.pattern()?);
+            if self.consume(&TokenKind::COMMA).is_none() {
+                break;
+            }
+        }
+
+        if self.consume(&TokenKind::RIGHTPAREN).is_none() {
+            return Err(Error::syntax_error(SyntaxError::S003, paren));
+        }
+
+        // Like expressions, a single parenthesized pattern is just a group
+        if elements.len() == 1 && !rest {
+            return Ok(elements.pop().unwrap());
+        }
+
+        Ok(Pattern::Tuple {
+            paren,
+            elements,
+            rest,
+        })
+    }
+
     fn expression_statement(&mut self) -> Result<Stmt, Error> {
-        let expr = self.expression()?;
-        if self.consume(&TokenKind::SEMICOLON).is_none() {
+        let expr = self.statement_expression()?;
+        if self.consume(&TokenKind::SEMICOLON).is_some() {
+            return Ok(Stmt::Expression { expr });
+        }
+
+        if !expr.is_block_like() {
             return Err(Error::syntax_error(
                 SyntaxError::S005(TokenKind::SEMICOLON),
                 self.previous().clone(),
             ));
         }
 
-        Ok(Stmt::Expression { expr })
+        // Like the tail of a block, the last statement is the value of the program
+        if self.is_at_end() {
+            Ok(Stmt::Expression { expr })
+        } else {
+            Ok(Stmt::BlockLike { expr })
+        }
     }
 
-    fn expression(&mut self) -> Result<Expr, Error> {
+    /// Parses the expression of an expression statement.
+    /// A statement starting with a block is just that block, so `{ a } (b, c)` is two statements
+    fn statement_expression(&mut self) -> Result<Expr, Error> {
         if self.consume(&TokenKind::LEFTBRACE).is_some() {
-            return self.block();
+            let expr = self.block()?;
+            if !matches!(expr, Expr::Set { .. }) {
+                return Ok(expr);
+            }
+
+            // Sets are regular operands, so go on with it as the start of a larger expression
+            self.operand = Some(expr);
+            return self.assigment();
         }
 
+        self.expression()
+    }
+
+    fn expression(&mut self) -> Result<Expr, Error> {
         if self.consume(&TokenKind::IF).is_some() {
             return self.if_expression();
         }
@@ -138,66 +374,114 @@ impl Parser {
     }
 
     fn block(&mut self) -> Result<Expr, Error> {
-        if self.consume(&TokenKind::RIGHTBRACE).is_some() {
-            return Ok(Expr::Block {
-                statements: Vec::new(),
-                finally: None,
+        // Type aliases declared in a block are only visible inside it
+        self.aliases = self.aliases.new_nested();
+        let block = self.block_contents();
+        self.aliases.pop();
+        block
+    }
+
+    fn block_contents(&mut self) -> Result<Expr, Error> {
+        let opening_token = self.previous().clone();
+
+        // `{}` is the empty block, so the empty set is written `{,}`
+        if self.consume(&TokenKind::COMMA).is_some() {
+            if self.consume(&TokenKind::RIGHTBRACE).is_none() {
+                return Err(Error::syntax_error(
+                    SyntaxError::S005(TokenKind::RIGHTBRACE),
+                    self.previous().clone(),
+                ));
+            }
+
+            return Ok(Expr::Set {
+                brace: opening_token,
+                elements: Vec::new(),
             });
         }
 
-        let opening_token = self.previous().clone();
         let mut statements = vec![];
+        loop {
+            if self.consume(&TokenKind::RIGHTBRACE).is_some() {
+                return Ok(Expr::Block {
+                    brace: opening_token,
+                    statements,
+                    finally: None,
+                });
+            }
 
-        // TODO: This can_find_before check seems like it would be awfully slow
-        while !self.is_at_end()
-            && self.can_find_before(&TokenKind::SEMICOLON, &TokenKind::RIGHTBRACE)
-        {
-            statements.push(self.statement()?);
-        }
+            if self.is_at_end() {
+                return Err(Error::syntax_error(SyntaxError::S010, opening_token));
+            }
 
-        if self.consume(&TokenKind::RIGHTBRACE).is_some() {
-            return Ok(Expr::Block {
-                statements,
-                finally: None,
-            });
+            if self.check(&TokenKind::TYPE)
+                || self.check(&TokenKind::LET)
+                || self.check(&TokenKind::AT)
+            {
+                statements.push(self.statement()?);
+                continue;
+            }
+
+            let expr = self.statement_expression()?;
+            if self.consume(&TokenKind::SEMICOLON).is_some() {
+                statements.push(Stmt::Expression { expr });
+                continue;
+            }
+
+            if self.consume(&TokenKind::RIGHTBRACE).is_some() {
+                return Ok(Expr::Block {
+                    brace: opening_token,
+                    statements,
+                    finally: Some(Box::new(expr)),
+                });
+            }
+
+            if statements.is_empty() && self.consume(&TokenKind::COMMA).is_some() {
+                return self.set(opening_token, expr);
+            }
+
+            if expr.is_block_like() {
+                statements.push(Stmt::BlockLike { expr });
+                continue;
+            }
+
+            if self.is_at_end() {
+                return Err(Error::syntax_error(SyntaxError::S010, opening_token));
+            }
+
+            return Err(Error::syntax_error(
+                SyntaxError::S005(TokenKind::SEMICOLON),
+                self.previous().clone(),
+            ));
         }
+    }
 
-        let finally = Some(Box::new(self.assigment()?));
+    fn set(&mut self, brace: Token, first: Expr) -> Result<Expr, Error> {
+        let mut elements = vec![first];
+        while !self.check(&TokenKind::RIGHTBRACE) {
+            elements.push(self.assigment()?);
+            if self.consume(&TokenKind::COMMA).is_none() {
+                break;
+            }
+        }
 
         if self.consume(&TokenKind::RIGHTBRACE).is_none() {
-            return Err(Error::syntax_error(SyntaxError::S010, opening_token));
+            return Err(Error::syntax_error(SyntaxError::S010, brace));
         }
 
-        Ok(Expr::Block {
-            statements,
-            finally,
-        })
+        Ok(Expr::Set { brace, elements })
     }
 
     fn if_expression(&mut self) -> Result<Expr, Error> {
         let keyword = self.previous().clone();
         let condition = Box::new(self.logic_or()?);
-
-        if self.consume(&TokenKind::LEFTBRACE).is_none() {
-            return Err(Error::syntax_error(
-                SyntaxError::S011,
-                self.previous().clone(),
-            ));
-        }
-        let then = Box::new(self.block()?);
+        let then = Box::new(self.body()?);
 
         let eelse = if self.consume(&TokenKind::ELSE).is_none() {
             None
         } else if self.consume(&TokenKind::IF).is_some() {
             Some(Box::new(self.if_expression()?))
         } else {
-            if self.consume(&TokenKind::LEFTBRACE).is_none() {
-                return Err(Error::syntax_error(
-                    SyntaxError::S011,
-                    self.previous().clone(),
-                ));
-            }
-            Some(Box::new(self.block()?))
+            Some(Box::new(self.body()?))
         };
 
         Ok(Expr::If {
@@ -211,14 +495,21 @@ impl Parser {
     fn while_expression(&mut self) -> Result<Expr, Error> {
         let keyword = self.previous().clone();
         let condition = Box::new(self.logic_or()?);
+        let body = Box::new(self.body()?);
+        Ok(Expr::While { keyword, condition, body })
+    }
+
+    /// Parses the block of an if or while, where a set like `{a,}` is not allowed
+    fn body(&mut self) -> Result<Expr, Error> {
+        let before = self.previous().clone();
         if self.consume(&TokenKind::LEFTBRACE).is_none() {
-            return Err(Error::syntax_error(
-                SyntaxError::S011,
-                self.previous().clone(),
-            ));
+            return Err(Error::syntax_error(SyntaxError::S011, before));
+        }
+
+        match self.block()? {
+            Expr::Set { .. } => Err(Error::syntax_error(SyntaxError::S011, before)),
+            block => Ok(block),
         }
-        let body = Box::new(self.block()?);
-        Ok(Expr::While { keyword, condition, body })
     }
 
     fn assigment(&mut self) -> Result<Expr, Error> {
@@ -281,26 +572,72 @@ impl Parser {
     }
 
     fn comparison(&mut self) -> Result<Expr, Error> {
-        let mut expr = self.term()?;
+        let mut expr = self.pipeline()?;
         while self.is_token_of_kind(&[
             TokenKind::LESS,
             TokenKind::LESSEQUAL,
             TokenKind::GREATER,
             TokenKind::GREATEREQUAL,
+            TokenKind::IN,
         ]) {
             expr = Expr::Binary {
                 operator: self.previous().clone(),
-                right: Box::new(self.term()?),
+                right: Box::new(self.pipeline()?),
                 left: Box::new(expr),
             }
         }
         Ok(expr)
     }
 
+    fn pipeline(&mut self) -> Result<Expr, Error> {
+        let mut expr = self.term()?;
+
+        while self.consume(&TokenKind::PIPEGREATER).is_some() {
+            let pipe = self.previous().clone();
+            let (callee, paren, mut arguments) = match self.call()? {
+                Expr::Call {
+                    callee,
+                    paren,
+                    arguments,
+                } => (callee, paren, arguments),
+                _ => return Err(Error::syntax_error(SyntaxError::S018, pipe)),
+            };
+
+            // `a |> f() + 1` would read as `f(a + 1)` to some and `f(a) + 1` to others
+            if self.check(&TokenKind::PLUS)
+                || self.check(&TokenKind::MINUS)
+                || self.check(&TokenKind::STAR)
+                || self.check(&TokenKind::SLASH)
+                || self.check(&TokenKind::PIPE)
+                || self.check(&TokenKind::AMPERSAND)
+                || self.check(&TokenKind::AS)
+            {
+                return Err(Error::syntax_error(SyntaxError::S019, self.peek().clone()));
+            }
+
+            arguments.insert(0, expr);
+            expr = Expr::Pipe {
+                pipe,
+                call: Box::new(Expr::Call {
+                    callee,
+                    paren,
+                    arguments,
+                }),
+            }
+        }
+
+        Ok(expr)
+    }
+
     fn term(&mut self) -> Result<Expr, Error> {
         let mut expr = self.factor()?;
 
-        while self.is_token_of_kind(&[TokenKind::MINUS, TokenKind::PLUS]) {
+        while self.is_token_of_kind(&[
+            TokenKind::MINUS,
+            TokenKind::PLUS,
+            TokenKind::PIPE,
+            TokenKind::AMPERSAND,
+        ]) {
             expr = Expr::Binary {
                 operator: self.previous().clone(),
                 right: Box::new(self.factor()?),
@@ -311,29 +648,126 @@ impl Parser {
     }
 
     fn factor(&mut self) -> Result<Expr, Error> {
-        let mut expr = self.unary()?;
+        let mut expr = self.cast()?;
 
         while self.is_token_of_kind(&[TokenKind::SLASH, TokenKind::STAR]) {
             expr = Expr::Binary {
                 operator: self.previous().clone(),
-                right: Box::new(self.unary()?),
+                right: Box::new(self.cast()?),
                 left: Box::new(expr),
             }
         }
         Ok(expr)
     }
 
+    fn cast(&mut self) -> Result<Expr, Error> {
+        let mut expr = self.unary()?;
+
+        while self.consume(&TokenKind::AS).is_some() {
+            expr = Expr::Cast {
+                keyword: self.previous().clone(),
+                expr: Box::new(expr),
+                ttype: self.ttype()?,
+                from: OnceCell::new(),
+            }
+        }
+        Ok(expr)
+    }
+
     fn unary(&mut self) -> Result<Expr, Error> {
-        if self.is_token_of_kind(&[TokenKind::BANG, TokenKind::MINUS]) {
+        // With an operand already parsed, a '-' is the binary operator after it
+        if self.operand.is_none() && self.is_token_of_kind(&[TokenKind::BANG, TokenKind::MINUS]) {
             return Ok(Expr::Unary {
                 operator: self.previous().clone(),
                 expr: Box::new(self.unary()?),
             });
         }
-        self.primary()
+        self.call()
+    }
+
+    fn call(&mut self) -> Result<Expr, Error> {
+        let mut expr = self.primary()?;
+
+        loop {
+            if self.consume(&TokenKind::LEFTPAREN).is_some() {
+                expr = self.finish_call(expr)?;
+            } else if self.consume(&TokenKind::LEFTBRACKET).is_some() {
+                expr = self.finish_index(expr)?;
+            } else {
+                break;
+            }
+        }
+
+        Ok(expr)
+    }
+
+    fn finish_index(&mut self, expr: Expr) -> Result<Expr, Error> {
+        let bracket = self.previous().clone();
+        let start = if self.check(&TokenKind::DOTDOT) {
+            None
+        } else {
+            Some(Box::new(self.expression()?))
+        };
+
+        let indexed = if self.consume(&TokenKind::DOTDOT).is_some() {
+            let end = if self.check(&TokenKind::RIGHTBRACKET) {
+                None
+            } else {
+                Some(Box::new(self.expression()?))
+            };
+
+            Expr::Slice {
+                expr: Box::new(expr),
+                bracket,
+                start,
+                end,
+            }
+        } else {
+            Expr::Index {
+                expr: Box::new(expr),
+                bracket,
+                // start is only skipped when followed by '..'
+                index: start.unwrap(),
+            }
+        };
+
+        if self.consume(&TokenKind::RIGHTBRACKET).is_none() {
+            return Err(Error::syntax_error(
+                SyntaxError::S005(TokenKind::RIGHTBRACKET),
+                self.previous().clone(),
+            ));
+        }
+
+        Ok(indexed)
+    }
+
+    fn finish_call(&mut self, callee: Expr) -> Result<Expr, Error> {
+        let paren = self.previous().clone();
+        let mut arguments = Vec::new();
+
+        if self.consume(&TokenKind::RIGHTPAREN).is_none() {
+            arguments.push(self.expression()?);
+            while self.consume(&TokenKind::COMMA).is_some() {
+                arguments.push(self.expression()?);
+            }
+
+            if self.consume(&TokenKind::RIGHTPAREN).is_none() {
+                return Err(Error::syntax_error(SyntaxError::S003, paren));
+            }
+        }
+
+        Ok(Expr::Call {
+            callee: Box::new(callee),
+            paren,
+            arguments,
+        })
     }
 
     fn primary(&mut self) -> Result<Expr, Error> {
+        if let Some(operand) = self.operand.take() {
+            return Ok(operand);
+        }
+
         if self.is_token_of_kind(&[
             TokenKind::FALSE,
             TokenKind::TRUE,
@@ -358,6 +792,10 @@ impl Parser {
             return self.tuple();
         }
 
+        if self.consume(&TokenKind::LEFTBRACE).is_some() {
+            return self.block();
+        }
+
         Err(Error::syntax_error(
             SyntaxError::S004,
             self.previous().clone(),
@@ -401,6 +839,26 @@ impl Parser {
             return Ok(Type::Text);
         }
 
+        if self.consume(&TokenKind::ANY).is_some() {
+            return Ok(Type::Any);
+        }
+
+        if let Some(name) = self.consume(&TokenKind::IDENTIFIER("".to_string())) {
+            let name = name.clone();
+            return self.alias_type(name);
+        }
+
+        if self.consume(&TokenKind::LEFTBRACE).is_some() {
+            let inner = self.ttype()?;
+            if self.consume(&TokenKind::RIGHTBRACE).is_none() {
+                return Err(Error::syntax_error(
+                    SyntaxError::S005(TokenKind::RIGHTBRACE),
+                    self.previous().clone(),
+                ));
+            }
+            return Ok(Type::Set(Box::new(inner)));
+        }
+
         if self.consume(&TokenKind::LEFTPAREN).is_some() {
             self.tuple_type()
         } else {
@@ -411,6 +869,66 @@ impl Parser {
         }
     }
 
+    fn alias_type(&mut self, name: Token) -> Result<Type, Error> {
+        if self.params.contains(&name.lexeme) {
+            return Ok(Type::Param(name.lexeme));
+        }
+
+        if self.declaring.as_ref() == Some(&name.lexeme) {
+            return Err(Error::syntax_error(SyntaxError::S013, name));
+        }
+
+        let (params, ttype) = match self.aliases.get(&name.lexeme) {
+            Some(alias) => alias.clone(),
+            None => return Err(Error::syntax_error(SyntaxError::S012, name)),
+        };
+
+        // Without parameters a '<' is not ours, as in `a as N < 2`
+        let mut arguments = Vec::new();
+        if !params.is_empty() && self.consume(&TokenKind::LESS).is_some() {
+            arguments.push(self.ttype()?);
+            while self.consume(&TokenKind::COMMA).is_some() {
+                arguments.push(self.ttype()?);
+            }
+
+            if self.consume(&TokenKind::GREATER).is_none() {
+                return Err(Error::syntax_error(
+                    SyntaxError::S005(TokenKind::GREATER),
+                    self.previous().clone(),
+                ));
+            }
+        }
+
+        if arguments.len() != params.len() {
+            return Err(Error::syntax_error(
+                SyntaxError::S014 {
+                    expected: params.len(),
+                    found: arguments.len(),
+                },
+                name,
+            ));
+        }
+
+        if params.is_empty() {
+            return Ok(Type::Alias(name.lexeme, Box::new(ttype)));
+        }
+
+        let display_name = format!(
+            "{}<{}>",
+            name.lexeme,
+            arguments
+                .iter()
+                .map(|argument| format!("{}", argument))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+        let bindings = params.into_iter().zip(arguments).collect::<Vec<_>>();
+        Ok(Type::Alias(
+            display_name,
+            Box::new(ttype.substitute(&bindings)),
+        ))
+    }
+
     fn tuple_type(&mut self) -> Result<Type, Error> {
         if self.consume(&TokenKind::RIGHTPAREN).is_some() {
             return Ok(Type::Tuple(Vec::new()));
@@ -460,22 +978,6 @@ impl Parser {
         self.peek().kind.is_same(kind)
     }
 
-    fn can_find_before(&self, find: &TokenKind, before: &TokenKind) -> bool {
-        let mut i = self.current;
-        loop {
-            let tok = &self.tokens.get(i).unwrap().kind;
-            if tok.is_same(find) {
-                return true;
-            }
-
-            if tok.is_same(before) || tok.is_same(&TokenKind::EOF) {
-                return false;
-            }
-
-            i += 1;
-        }
-    }
-
     fn is_token_of_kind(&mut self, kinds: &[TokenKind]) -> bool {
         for kind in kinds {
             if self.check(kind) {
@@ -743,4 +1245,504 @@ mod test {
         assert_eq!(result, target);
         Ok(())
     }
+
+    #[test]
+    fn generic_type_alias() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(TYPE, 0, 0, "type"),
+            Token::new(IDENTIFIER("Pair".to_string()), 0, 5, "Pair"),
+            Token::new(LESS, 0, 9, "<"),
+            Token::new(IDENTIFIER("T".to_string()), 0, 10, "T"),
+            Token::new(GREATER, 0, 11, ">"),
+            Token::new(EQUAL, 0, 13, "="),
+            Token::new(LEFTPAREN, 0, 15, "("),
+            Token::new(IDENTIFIER("T".to_string()), 0, 16, "T"),
+            Token::new(COMMA, 0, 17, ","),
+            Token::new(IDENTIFIER("T".to_string()), 0, 19, "T"),
+            Token::new(RIGHTPAREN, 0, 20, ")"),
+            Token::new(SEMICOLON, 0, 21, ";"),
+            Token::new(LET, 1, 0, "let"),
+            Token::new(IDENTIFIER("p".to_string()), 1, 4, "p"),
+            Token::new(COLON, 1, 5, ":"),
+            Token::new(IDENTIFIER("Pair".to_string()), 1, 7, "Pair"),
+            Token::new(LESS, 1, 11, "<"),
+            Token::new(NUMBERKW, 1, 12, "number"),
+            Token::new(GREATER, 1, 18, ">"),
+            Token::new(EQUAL, 1, 20, "="),
+            Token::new(NUMBER(1.0), 1, 22, "1"),
+            Token::new(SEMICOLON, 1, 23, ";"),
+            Token::new(EOF, 2, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone())?;
+        let target = vec![
+            Stmt::Type {
+                name: tokens.get(1).unwrap().clone(),
+                ttype: Type::Tuple(vec![
+                    Type::Param("T".to_string()),
+                    Type::Param("T".to_string()),
+                ]),
+            },
+            Stmt::Let {
+                attributes: vec![],
+                pattern: Pattern::Binding {
+                    name: tokens.get(13).unwrap().clone(),
+                },
+                ttype: Some(Type::Alias(
+                    "Pair<number>".to_string(),
+                    Box::new(Type::Tuple(vec![Type::Number, Type::Number])),
+                )),
+                initializer: Literal {
+                    value: tokens.get(20).unwrap().clone(),
+                },
+            },
+        ];
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn error_on_cyclic_type_alias() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(TYPE, 0, 0, "type"),
+            Token::new(IDENTIFIER("List".to_string()), 0, 5, "List"),
+            Token::new(EQUAL, 0, 10, "="),
+            Token::new(LEFTPAREN, 0, 12, "("),
+            Token::new(NUMBERKW, 0, 13, "number"),
+            Token::new(COMMA, 0, 19, ","),
+            Token::new(IDENTIFIER("List".to_string()), 0, 21, "List"),
+            Token::new(RIGHTPAREN, 0, 25, ")"),
+            Token::new(SEMICOLON, 0, 26, ";"),
+            Token::new(EOF, 1, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone());
+        let target = Err(vec![Error::syntax_error(
+            SyntaxError::S013,
+            tokens.get(6).unwrap().clone(),
+        )]);
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn tuple_pattern() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(LET, 0, 0, "let"),
+            Token::new(LEFTPAREN, 0, 4, "("),
+            Token::new(IDENTIFIER("a".to_string()), 0, 5, "a"),
+            Token::new(COMMA, 0, 6, ","),
+            Token::new(IDENTIFIER("_".to_string()), 0, 8, "_"),
+            Token::new(COMMA, 0, 9, ","),
+            Token::new(DOTDOTDOT, 0, 11, "..."),
+            Token::new(RIGHTPAREN, 0, 14, ")"),
+            Token::new(EQUAL, 0, 16, "="),
+            Token::new(IDENTIFIER("t".to_string()), 0, 18, "t"),
+            Token::new(SEMICOLON, 0, 19, ";"),
+            Token::new(EOF, 1, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone())?;
+        let target = vec![Stmt::Let {
+            attributes: vec![],
+            pattern: Pattern::Tuple {
+                paren: tokens.get(1).unwrap().clone(),
+                elements: vec![
+                    Pattern::Binding {
+                        name: tokens.get(2).unwrap().clone(),
+                    },
+                    Pattern::Wildcard {
+                        token: tokens.get(4).unwrap().clone(),
+                    },
+                ],
+                rest: true,
+            },
+            ttype: None,
+            initializer: Variable {
+                name: tokens.get(9).unwrap().clone(),
+            },
+        }];
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn attributes() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(AT, 0, 0, "@"),
+            Token::new(IDENTIFIER("deprecated".to_string()), 0, 1, "deprecated"),
+            Token::new(LEFTPAREN, 0, 11, "("),
+            Token::new(STRING("old".to_string()), 0, 12, "\"old\""),
+            Token::new(RIGHTPAREN, 0, 17, ")"),
+            Token::new(LET, 1, 0, "let"),
+            Token::new(IDENTIFIER("x".to_string()), 1, 4, "x"),
+            Token::new(EQUAL, 1, 6, "="),
+            Token::new(NUMBER(1.0), 1, 8, "1"),
+            Token::new(SEMICOLON, 1, 9, ";"),
+            Token::new(EOF, 2, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone())?;
+        let target = vec![Stmt::Let {
+            attributes: vec![Attribute::Deprecated {
+                name: tokens.get(1).unwrap().clone(),
+                message: Some("old".to_string()),
+            }],
+            pattern: Pattern::Binding {
+                name: tokens.get(6).unwrap().clone(),
+            },
+            ttype: None,
+            initializer: Literal {
+                value: tokens.get(8).unwrap().clone(),
+            },
+        }];
+        assert_eq!(result, target);
+
+        let tokens = vec![
+            Token::new(AT, 0, 0, "@"),
+            Token::new(IDENTIFIER("inline".to_string()), 0, 1, "inline"),
+            Token::new(LEFTPAREN, 0, 7, "("),
+            Token::new(STRING("x".to_string()), 0, 8, "\"x\""),
+            Token::new(COMMA, 0, 11, ","),
+            Token::new(STRING("y".to_string()), 0, 13, "\"y\""),
+            Token::new(RIGHTPAREN, 0, 16, ")"),
+            Token::new(LET, 0, 18, "let"),
+            Token::new(IDENTIFIER("y".to_string()), 0, 22, "y"),
+            Token::new(EQUAL, 0, 24, "="),
+            Token::new(NUMBER(2.0), 0, 26, "2"),
+            Token::new(SEMICOLON, 0, 27, ";"),
+            Token::new(EOF, 1, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone());
+        let target = Err(vec![Error::syntax_error(
+            SyntaxError::S016,
+            tokens.get(1).unwrap().clone(),
+        )]);
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn pipeline() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(IDENTIFIER("x".to_string()), 0, 0, "x"),
+            Token::new(PIPEGREATER, 0, 2, "|>"),
+            Token::new(IDENTIFIER("assert".to_string()), 0, 5, "assert"),
+            Token::new(LEFTPAREN, 0, 11, "("),
+            Token::new(STRING("msg".to_string()), 0, 12, "\"msg\""),
+            Token::new(RIGHTPAREN, 0, 17, ")"),
+            Token::new(SEMICOLON, 0, 18, ";"),
+            Token::new(EOF, 1, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone())?;
+        let target = vec![Stmt::Expression {
+            expr: Pipe {
+                pipe: tokens.get(1).unwrap().clone(),
+                call: Box::new(Call {
+                    callee: Box::new(Variable {
+                        name: tokens.get(2).unwrap().clone(),
+                    }),
+                    paren: tokens.get(3).unwrap().clone(),
+                    arguments: vec![
+                        Variable {
+                            name: tokens.get(0).unwrap().clone(),
+                        },
+                        Literal {
+                            value: tokens.get(4).unwrap().clone(),
+                        },
+                    ],
+                }),
+            },
+        }];
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn block_like_statement_without_semicolon() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(LEFTBRACE, 0, 0, "{"),
+            Token::new(IDENTIFIER("a".to_string()), 0, 2, "a"),
+            Token::new(RIGHTBRACE, 0, 4, "}"),
+            Token::new(LEFTPAREN, 1, 0, "("),
+            Token::new(NUMBER(1.0), 1, 1, "1"),
+            Token::new(COMMA, 1, 2, ","),
+            Token::new(NUMBER(2.0), 1, 4, "2"),
+            Token::new(RIGHTPAREN, 1, 5, ")"),
+            Token::new(SEMICOLON, 1, 6, ";"),
+            Token::new(EOF, 2, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone())?;
+        let target = vec![
+            Stmt::BlockLike {
+                expr: Block {
+                    brace: tokens.get(0).unwrap().clone(),
+                    statements: vec![],
+                    finally: Some(Box::new(Variable {
+                        name: tokens.get(1).unwrap().clone(),
+                    })),
+                },
+            },
+            Stmt::Expression {
+                expr: Tuple {
+                    inners: vec![
+                        Literal {
+                            value: tokens.get(4).unwrap().clone(),
+                        },
+                        Literal {
+                            value: tokens.get(6).unwrap().clone(),
+                        },
+                    ],
+                },
+            },
+        ];
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn type_alias_is_scoped_to_block() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(LEFTBRACE, 0, 0, "{"),
+            Token::new(TYPE, 0, 2, "type"),
+            Token::new(IDENTIFIER("Q".to_string()), 0, 7, "Q"),
+            Token::new(EQUAL, 0, 9, "="),
+            Token::new(NUMBERKW, 0, 11, "number"),
+            Token::new(SEMICOLON, 0, 17, ";"),
+            Token::new(RIGHTBRACE, 0, 19, "}"),
+            Token::new(LET, 1, 0, "let"),
+            Token::new(IDENTIFIER("y".to_string()), 1, 4, "y"),
+            Token::new(COLON, 1, 5, ":"),
+            Token::new(IDENTIFIER("Q".to_string()), 1, 7, "Q"),
+            Token::new(EQUAL, 1, 9, "="),
+            Token::new(NUMBER(2.0), 1, 11, "2"),
+            Token::new(SEMICOLON, 1, 12, ";"),
+            Token::new(EOF, 2, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone());
+        let target = Err(vec![Error::syntax_error(
+            SyntaxError::S012,
+            tokens.get(10).unwrap().clone(),
+        )]);
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn empty_set_literal() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(LEFTBRACE, 0, 0, "{"),
+            Token::new(COMMA, 0, 1, ","),
+            Token::new(RIGHTBRACE, 0, 2, "}"),
+            Token::new(SEMICOLON, 0, 3, ";"),
+            Token::new(EOF, 1, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone())?;
+        let target = vec![Stmt::Expression {
+            expr: Set {
+                brace: tokens.get(0).unwrap().clone(),
+                elements: vec![],
+            },
+        }];
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn pipeline_precedence() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(IDENTIFIER("a".to_string()), 0, 0, "a"),
+            Token::new(PLUS, 0, 2, "+"),
+            Token::new(IDENTIFIER("b".to_string()), 0, 4, "b"),
+            Token::new(PIPEGREATER, 0, 6, "|>"),
+            Token::new(IDENTIFIER("f".to_string()), 0, 9, "f"),
+            Token::new(LEFTPAREN, 0, 10, "("),
+            Token::new(RIGHTPAREN, 0, 11, ")"),
+            Token::new(EQUALEQUAL, 0, 13, "=="),
+            Token::new(IDENTIFIER("c".to_string()), 0, 16, "c"),
+            Token::new(SEMICOLON, 0, 17, ";"),
+            Token::new(EOF, 1, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone())?;
+        let target = vec![Stmt::Expression {
+            expr: Binary {
+                operator: tokens.get(7).unwrap().clone(),
+                left: Box::new(Pipe {
+                    pipe: tokens.get(3).unwrap().clone(),
+                    call: Box::new(Call {
+                        callee: Box::new(Variable {
+                            name: tokens.get(4).unwrap().clone(),
+                        }),
+                        paren: tokens.get(5).unwrap().clone(),
+                        arguments: vec![Binary {
+                            operator: tokens.get(1).unwrap().clone(),
+                            left: Box::new(Variable {
+                                name: tokens.get(0).unwrap().clone(),
+                            }),
+                            right: Box::new(Variable {
+                                name: tokens.get(2).unwrap().clone(),
+                            }),
+                        }],
+                    }),
+                }),
+                right: Box::new(Variable {
+                    name: tokens.get(8).unwrap().clone(),
+                }),
+            },
+        }];
+        assert_eq!(result, target);
+
+        let tokens = vec![
+            Token::new(IDENTIFIER("a".to_string()), 0, 0, "a"),
+            Token::new(PIPEGREATER, 0, 2, "|>"),
+            Token::new(IDENTIFIER("f".to_string()), 0, 5, "f"),
+            Token::new(LEFTPAREN, 0, 6, "("),
+            Token::new(RIGHTPAREN, 0, 7, ")"),
+            Token::new(PLUS, 0, 9, "+"),
+            Token::new(NUMBER(1.0), 0, 11, "1"),
+            Token::new(SEMICOLON, 0, 12, ";"),
+            Token::new(EOF, 1, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone());
+        let target = Err(vec![Error::syntax_error(
+            SyntaxError::S019,
+            tokens.get(5).unwrap().clone(),
+        )]);
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn set_statement_continues_expression() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(LEFTBRACE, 0, 0, "{"),
+            Token::new(NUMBER(1.0), 0, 1, "1"),
+            Token::new(COMMA, 0, 2, ","),
+            Token::new(RIGHTBRACE, 0, 3, "}"),
+            Token::new(MINUS, 0, 5, "-"),
+            Token::new(LEFTBRACE, 0, 7, "{"),
+            Token::new(COMMA, 0, 8, ","),
+            Token::new(RIGHTBRACE, 0, 9, "}"),
+            Token::new(SEMICOLON, 0, 10, ";"),
+            Token::new(EOF, 1, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone())?;
+        let target = vec![Stmt::Expression {
+            expr: Binary {
+                operator: tokens.get(4).unwrap().clone(),
+                left: Box::new(Set {
+                    brace: tokens.get(0).unwrap().clone(),
+                    elements: vec![Literal {
+                        value: tokens.get(1).unwrap().clone(),
+                    }],
+                }),
+                right: Box::new(Set {
+                    brace: tokens.get(5).unwrap().clone(),
+                    elements: vec![],
+                }),
+            },
+        }];
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn set_as_if_body() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(IF, 0, 0, "if"),
+            Token::new(TRUE, 0, 3, "true"),
+            Token::new(LEFTBRACE, 0, 8, "{"),
+            Token::new(NUMBER(1.0), 0, 9, "1"),
+            Token::new(COMMA, 0, 10, ","),
+            Token::new(RIGHTBRACE, 0, 11, "}"),
+            Token::new(EOF, 1, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone());
+        let target = Err(vec![Error::syntax_error(
+            SyntaxError::S011,
+            tokens.get(1).unwrap().clone(),
+        )]);
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn cast_to_alias_before_comparison() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(TYPE, 0, 0, "type"),
+            Token::new(IDENTIFIER("N".to_string()), 0, 5, "N"),
+            Token::new(EQUAL, 0, 7, "="),
+            Token::new(NUMBERKW, 0, 9, "number"),
+            Token::new(SEMICOLON, 0, 15, ";"),
+            Token::new(IDENTIFIER("a".to_string()), 1, 0, "a"),
+            Token::new(AS, 1, 2, "as"),
+            Token::new(IDENTIFIER("N".to_string()), 1, 5, "N"),
+            Token::new(LESS, 1, 7, "<"),
+            Token::new(NUMBER(2.0), 1, 9, "2"),
+            Token::new(SEMICOLON, 1, 10, ";"),
+            Token::new(EOF, 2, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone())?;
+        let alias = Type::Alias("N".to_string(), Box::new(Type::Number));
+        let target = vec![
+            Stmt::Type {
+                name: tokens.get(1).unwrap().clone(),
+                ttype: Type::Number,
+            },
+            Stmt::Expression {
+                expr: Binary {
+                    operator: tokens.get(8).unwrap().clone(),
+                    left: Box::new(Cast {
+                        expr: Box::new(Variable {
+                            name: tokens.get(5).unwrap().clone(),
+                        }),
+                        keyword: tokens.get(6).unwrap().clone(),
+                        ttype: alias,
+                        from: OnceCell::new(),
+                    }),
+                    right: Box::new(Literal {
+                        value: tokens.get(9).unwrap().clone(),
+                    }),
+                },
+            },
+        ];
+        assert_eq!(result, target);
+        Ok(())
+    }
+
+    #[test]
+    fn rest_pattern_before_end() -> Result<(), Vec<Error>> {
+        let tokens = vec![
+            Token::new(LET, 0, 0, "let"),
+            Token::new(LEFTPAREN, 0, 4, "("),
+            Token::new(IDENTIFIER("a".to_string()), 0, 5, "a"),
+            Token::new(COMMA, 0, 6, ","),
+            Token::new(DOTDOTDOT, 0, 8, "..."),
+            Token::new(COMMA, 0, 11, ","),
+            Token::new(IDENTIFIER("b".to_string()), 0, 13, "b"),
+            Token::new(RIGHTPAREN, 0, 14, ")"),
+            Token::new(EQUAL, 0, 16, "="),
+            Token::new(IDENTIFIER("t".to_string()), 0, 18, "t"),
+            Token::new(SEMICOLON, 0, 19, ";"),
+            Token::new(EOF, 1, 0, "\n"),
+        ];
+
+        let result = Parser::new().parse(tokens.clone());
+        let target = Err(vec![Error::syntax_error(
+            SyntaxError::S020,
+            tokens.get(4).unwrap().clone(),
+        )]);
+        assert_eq!(result, target);
+        Ok(())
+    }
 }
@@ -1,17 +1,23 @@
 use crate::{
     environment::Environment,
-    error::{language_error, Error, TypeError},
-    types::{Expr, Stmt, TokenKind, Type},
+    error::{language_error, Error, TypeError, Warning},
+    types::{Attribute, Builtin, Expr, Pattern, Stmt, Token, TokenKind, Type},
 };
 
 pub struct Typechecker {
     scope: Environment<Type>,
+    /// Attributes of the declaration each name in `scope` was bound by
+    attributes: Environment<Vec<Attribute>>,
+    /// Warnings found while checking, these do not fail the check
+    pub warnings: Vec<Error>,
 }
 
 impl Typechecker {
     pub fn new() -> Self {
         Self {
             scope: Environment::new(),
+            attributes: Environment::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -38,30 +44,132 @@ impl Typechecker {
             Stmt::Expression { expr } => {
                 self.visit_expression(expr)?;
             }
+            Stmt::BlockLike { expr } => {
+                let found = self.visit_expression(expr)?;
+                if !Type::Tuple(Vec::new()).accepts(&found) {
+                    let token = match expr {
+                        Expr::If { keyword, .. } | Expr::While { keyword, .. } => keyword,
+                        Expr::Block { brace, .. } => brace,
+                        _ => language_error("block-like statement that is not block-like"),
+                    };
+                    return Err(vec![Error::type_error(
+                        TypeError::T006 {
+                            expected: Type::Tuple(Vec::new()),
+                            found,
+                        },
+                        token.clone(),
+                    )]);
+                }
+            }
             Stmt::Let {
-                name,
+                attributes,
+                pattern,
                 ttype,
                 initializer,
             } => {
                 let initializer_type = self.visit_expression(initializer)?;
                 if let Some(ttype) = ttype {
-                    if initializer_type != *ttype {
-                        return Err(vec![Error::type_error(
-                            TypeError::T003 {
+                    if !ttype.accepts(&initializer_type) {
+                        if let Some(error) = block_as_set(initializer, ttype) {
+                            return Err(vec![error]);
+                        }
+
+                        let errno = match pattern {
+                            Pattern::Binding { .. } => TypeError::T003 {
                                 declared_as: ttype.clone(),
-                                initialized_as: initializer_type.clone(),
+                                initialized_as: initializer_type,
+                            },
+                            _ => TypeError::T006 {
+                                expected: ttype.clone(),
+                                found: initializer_type,
                             },
-                            name.clone(),
-                        )]);
+                        };
+                        return Err(vec![Error::type_error(errno, pattern.token().clone())]);
                     }
                 }
 
-                self.scope.define(&name.lexeme, initializer_type);
+                if ttype.is_none() && is_empty_set(&initializer_type) {
+                    return Err(vec![Error::type_error(
+                        TypeError::T015,
+                        pattern.token().clone(),
+                    )]);
+                }
+
+                let bindings = pattern.bindings();
+                for (i, name) in bindings.iter().enumerate() {
+                    if bindings[..i]
+                        .iter()
+                        .any(|bound| bound.lexeme == name.lexeme)
+                    {
+                        return Err(vec![Error::type_error(TypeError::T016, (*name).clone())]);
+                    }
+                }
+
+                let ttype = ttype.clone().unwrap_or(initializer_type);
+                self.bind(pattern, ttype, attributes)?;
             }
+            // Aliases are resolved by the parser
+            Stmt::Type { .. } => {}
         }
         Ok(())
     }
 
+    /// Defines the names bound by `pattern` when it is matched against a value of type `ttype`
+    fn bind(
+        &mut self,
+        pattern: &Pattern,
+        ttype: Type,
+        attributes: &[Attribute],
+    ) -> Result<(), Vec<Error>> {
+        match pattern {
+            Pattern::Binding { name } => {
+                self.scope.define(&name.lexeme, ttype);
+                self.attributes.define(&name.lexeme, attributes.to_vec());
+                Ok(())
+            }
+            Pattern::Wildcard { .. } => Ok(()),
+            Pattern::Literal { value } => {
+                Err(vec![Error::type_error(TypeError::T011, value.clone())])
+            }
+            Pattern::Tuple {
+                paren,
+                elements,
+                rest,
+            } => {
+                let inners = match ttype.resolved() {
+                    Type::Tuple(inners)
+                        if inners.len() == elements.len()
+                            || (*rest && inners.len() >= elements.len()) =>
+                    {
+                        inners.clone()
+                    }
+                    _ => {
+                        return Err(vec![Error::type_error(
+                            TypeError::T012 {
+                                found: ttype,
+                                extracted: elements.len(),
+                            },
+                            paren.clone(),
+                        )])
+                    }
+                };
+
+                let mut errors = Vec::new();
+                for (element, inner) in elements.iter().zip(inners) {
+                    if let Err(mut errs) = self.bind(element, inner, attributes) {
+                        errors.append(&mut errs);
+                    }
+                }
+
+                if !errors.is_empty() {
+                    Err(errors)
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
     pub fn visit_expression(&mut self, e: &Expr) -> Result<Type, Vec<Error>> {
         match e {
             Expr::Literal { value } => Ok(Type::from(value)),
@@ -72,12 +180,41 @@ impl Typechecker {
                     .map(|expr| self.visit_expression(expr))
                     .collect::<Result<Vec<Type>, Vec<Error>>>()?,
             )),
+            Expr::Set { brace, elements } => {
+                let element_types = elements
+                    .iter()
+                    .map(|element| self.visit_expression(element))
+                    .collect::<Result<Vec<Type>, Vec<Error>>>()?;
+                // The element type of `{,}` is decided by where it is used
+                let mut expected = Type::Never;
+                let mut errors = Vec::new();
+                for found in element_types {
+                    match expected.join(&found) {
+                        Some(joined) => expected = joined,
+                        None => errors.push(Error::type_error(
+                            TypeError::T006 {
+                                expected: expected.clone(),
+                                found,
+                            },
+                            brace.clone(),
+                        )),
+                    }
+                }
+
+                if !errors.is_empty() {
+                    Err(errors)
+                } else {
+                    Ok(Type::Set(Box::new(expected)))
+                }
+            }
             Expr::Logical {
                 operator,
                 left,
                 right,
             } => match (self.visit_expression(left)?, self.visit_expression(right)?) {
-                (Type::Bool, Type::Bool) => Ok(Type::Bool),
+                (left, right) if Type::Bool.accepts(&left) && Type::Bool.accepts(&right) => {
+                    Ok(Type::Bool)
+                }
                 (left, right) => Err(vec![Error::type_error(
                     TypeError::T001 { left, right },
                     operator.clone(),
@@ -86,14 +223,14 @@ impl Typechecker {
             Expr::Unary { operator, expr } => {
                 let operand = self.visit_expression(expr)?;
                 match operator.kind {
-                    TokenKind::BANG => match operand {
+                    TokenKind::BANG => match operand.resolved() {
                         Type::Bool => Ok(Type::Bool),
                         _ => Err(vec![Error::type_error(
                             TypeError::T002 { operand },
                             operator.clone(),
                         )]),
                     },
-                    TokenKind::MINUS => match operand {
+                    TokenKind::MINUS => match operand.resolved() {
                         Type::Number => Ok(Type::Number),
                         _ => Err(vec![Error::type_error(
                             TypeError::T002 { operand },
@@ -103,24 +240,68 @@ impl Typechecker {
                     _ => language_error(&format!("unknown unary operator")),
                 }
             }
+            Expr::Cast {
+                expr,
+                keyword,
+                ttype,
+                from: from_cell,
+            } => {
+                let from = self.visit_expression(expr)?;
+                if from.casts_to(ttype) {
+                    // Already set if this cast was checked before, the type is the same
+                    let _ = from_cell.set(from);
+                    Ok(ttype.clone())
+                } else {
+                    Err(vec![Error::type_error(
+                        TypeError::T013 {
+                            from,
+                            to: ttype.clone(),
+                        },
+                        keyword.clone(),
+                    )])
+                }
+            }
             Expr::Binary {
                 operator,
-                left,
-                right,
+                left: left_expr,
+                right: right_expr,
             } => {
-                let left = self.visit_expression(left)?;
-                let right = self.visit_expression(right)?;
-                match operator.kind {
-                    TokenKind::PLUS => match (left, right) {
+                let left = self.visit_expression(left_expr)?;
+                let right = self.visit_expression(right_expr)?;
+                let is_set = matches!(left.resolved(), Type::Set(_));
+
+                // `{a}` next to a set reads like a set literal but is a block
+                let block_as_set = match operator.kind {
+                    TokenKind::IN => block_as_set(right_expr, &Type::Set(Box::new(left.clone()))),
+                    _ => {
+                        block_as_set(right_expr, &left).or_else(|| block_as_set(left_expr, &right))
+                    }
+                };
+
+                let result = match operator.kind {
+                    TokenKind::PLUS => match (left.resolved(), right.resolved()) {
                         (Type::Number, Type::Number) => Ok(Type::Number),
                         (Type::Text, Type::Text) => Ok(Type::Text),
-                        (left, right) => Err(vec![Error::type_error(
+                        _ => Err(vec![Error::type_error(
                             TypeError::T001 { left, right },
                             operator.clone(),
                         )]),
                     },
+                    // The empty set gets its element type from the other operand
+                    TokenKind::MINUS | TokenKind::PIPE | TokenKind::AMPERSAND if is_set => {
+                        left.join(&right).ok_or_else(|| {
+                            vec![Error::type_error(
+                                TypeError::T001 { left, right },
+                                operator.clone(),
+                            )]
+                        })
+                    }
+                    TokenKind::PIPE | TokenKind::AMPERSAND => Err(vec![Error::type_error(
+                        TypeError::T001 { left, right },
+                        operator.clone(),
+                    )]),
                     TokenKind::MINUS | TokenKind::STAR | TokenKind::SLASH => {
-                        if left != Type::Number || right != Type::Number {
+                        if !Type::Number.accepts(&left) || !Type::Number.accepts(&right) {
                             Err(vec![Error::type_error(
                                 TypeError::T001 { left, right },
                                 operator.clone(),
@@ -133,7 +314,10 @@ impl Typechecker {
                     | TokenKind::LESSEQUAL
                     | TokenKind::GREATER
                     | TokenKind::GREATEREQUAL => {
-                        if left != Type::Number || right != Type::Number {
+                        // Comparing sets checks for subsets and supersets
+                        if is_set && left.join(&right).is_some() {
+                            Ok(Type::Bool)
+                        } else if !Type::Number.accepts(&left) || !Type::Number.accepts(&right) {
                             Err(vec![Error::type_error(
                                 TypeError::T001 { left, right },
                                 operator.clone(),
@@ -142,8 +326,15 @@ impl Typechecker {
                             Ok(Type::Bool)
                         }
                     }
+                    TokenKind::IN => match right.resolved() {
+                        Type::Set(element) if element.join(&left).is_some() => Ok(Type::Bool),
+                        _ => Err(vec![Error::type_error(
+                            TypeError::T001 { left, right },
+                            operator.clone(),
+                        )]),
+                    },
                     TokenKind::EQUALEQUAL | TokenKind::BANGEQUAL => {
-                        if left != right {
+                        if left.join(&right).is_none() {
                             Err(vec![Error::type_error(
                                 TypeError::T001 { left, right },
                                 operator.clone(),
@@ -153,26 +344,72 @@ impl Typechecker {
                         }
                     }
                     _ => language_error(&format!("unknown binary operator '{}'", operator.lexeme)),
+                };
+
+                match block_as_set {
+                    Some(error) if result.is_err() => Err(vec![error]),
+                    _ => result,
                 }
             }
             Expr::Variable { name } => {
-                if let Some(ttype) = self.scope.get(&name.lexeme) {
-                    Ok(ttype.clone())
+                if let Some(ttype) = self.scope.get(&name.lexeme).cloned() {
+                    self.check_deprecated(name);
+                    Ok(ttype)
                 } else {
                     Err(vec![Error::type_error(TypeError::T004, name.clone())])
                 }
             }
+            Expr::Call {
+                callee,
+                paren,
+                arguments,
+            } => self.check_call(callee, paren, arguments, None),
+            Expr::Pipe { pipe, call } => match call.as_ref() {
+                Expr::Call {
+                    callee,
+                    paren,
+                    arguments,
+                } => self.check_call(callee, paren, arguments, Some(pipe)),
+                _ => language_error("pipe into non-call that was not parsed as a call"),
+            },
+            Expr::Index {
+                expr,
+                bracket,
+                index,
+            } => {
+                let indexed = self.visit_expression(expr)?;
+                let index = self.visit_expression(index)?;
+                self.check_indexing(bracket, indexed, vec![index])
+            }
+            Expr::Slice {
+                expr,
+                bracket,
+                start,
+                end,
+            } => {
+                let indexed = self.visit_expression(expr)?;
+                let bounds = start
+                    .iter()
+                    .chain(end.iter())
+                    .map(|bound| self.visit_expression(bound))
+                    .collect::<Result<Vec<Type>, Vec<Error>>>()?;
+                self.check_indexing(bracket, indexed, bounds)
+            }
             Expr::Assign { name, expr } => {
                 let assigned_to = self.visit_expression(expr)?;
+                self.check_deprecated(name);
                 if let Some(declared_as) = self.scope.get(&name.lexeme) {
-                    return if *declared_as != assigned_to {
-                        Err(vec![Error::type_error(
-                            TypeError::T005 {
-                                declared_as: declared_as.clone(),
-                                assigned_to,
-                            },
-                            name.clone(),
-                        )])
+                    return if !declared_as.accepts(&assigned_to) {
+                        let error = block_as_set(expr, declared_as).unwrap_or_else(|| {
+                            Error::type_error(
+                                TypeError::T005 {
+                                    declared_as: declared_as.clone(),
+                                    assigned_to,
+                                },
+                                name.clone(),
+                            )
+                        });
+                        Err(vec![error])
                     } else {
                         Ok(assigned_to)
                     };
@@ -183,8 +420,10 @@ impl Typechecker {
             Expr::Block {
                 statements,
                 finally,
+                ..
             } => {
                 self.scope = self.scope.new_nested();
+                self.attributes = self.attributes.new_nested();
                 for stmt in statements {
                     self.visit_statement(stmt)?;
                 }
@@ -194,6 +433,7 @@ impl Typechecker {
                     Type::Tuple(Vec::new())
                 };
                 self.scope.pop();
+                self.attributes.pop();
                 Ok(val)
             }
             Expr::If {
@@ -204,7 +444,7 @@ impl Typechecker {
             } => {
                 let mut errors = Vec::new();
                 let condition_t = self.visit_expression(condition)?;
-                if condition_t != Type::Bool {
+                if !Type::Bool.accepts(&condition_t) {
                     errors.push(Error::type_error(
                         TypeError::T006 {
                             expected: Type::Bool,
@@ -220,7 +460,9 @@ impl Typechecker {
                 } else {
                     Type::Tuple(Vec::new())
                 };
-                if then != eelse_t {
+                // A diverging branch takes the type of the other one
+                let joined = then.join(&eelse_t);
+                if joined.is_none() {
                     if eelse.is_none() {
                         errors.push(Error::type_error(
                             TypeError::T007 { then: then.clone() },
@@ -230,17 +472,16 @@ impl Typechecker {
                         errors.push(Error::type_error(
                             TypeError::T008 {
                                 then: then.clone(),
-                                eelse: eelse_t,
+                                eelse: eelse_t.clone(),
                             },
                             keyword.clone(),
                         ));
                     }
                 };
 
-                if errors.len() > 0 {
-                    Err(errors)
-                } else {
-                    Ok(then)
+                match joined {
+                    Some(joined) if errors.is_empty() => Ok(joined),
+                    _ => Err(errors),
                 }
             }
             Expr::While {
@@ -250,7 +491,7 @@ impl Typechecker {
             } => {
                 let mut errors = Vec::new();
                 let condition_t = self.visit_expression(condition)?;
-                if condition_t != Type::Bool {
+                if !Type::Bool.accepts(&condition_t) {
                     errors.push(Error::type_error(
                         TypeError::T006 {
                             expected: Type::Bool,
@@ -261,7 +502,7 @@ impl Typechecker {
                 }
 
                 let body_t = self.visit_expression(body)?;
-                if body_t != Type::Tuple(Vec::new()) {
+                if !Type::Tuple(Vec::new()).accepts(&body_t) {
                     errors.push(Error::type_error(
                         TypeError::T006 {
                             expected: Type::Tuple(Vec::new()),
@@ -279,13 +520,143 @@ impl Typechecker {
             }
         }
     }
+
+    /// Checks a call to `callee`. For `left |> callee(..)` the error for a wrong `left`
+    /// points at the `pipe`
+    fn check_call(
+        &mut self,
+        callee: &Expr,
+        paren: &Token,
+        arguments: &[Expr],
+        pipe: Option<&Token>,
+    ) -> Result<Type, Vec<Error>> {
+        let name = match callee {
+            Expr::Variable { name } => name,
+            _ => {
+                return Err(vec![Error::type_error(TypeError::T009, paren.clone())]);
+            }
+        };
+
+        let builtin = match Builtin::from_name(&name.lexeme) {
+            Some(builtin) => builtin,
+            None if self.scope.get(&name.lexeme).is_some() => {
+                return Err(vec![Error::type_error(TypeError::T009, name.clone())]);
+            }
+            None => return Err(vec![Error::type_error(TypeError::T004, name.clone())]),
+        };
+
+        let argument_types = arguments
+            .iter()
+            .map(|argument| self.visit_expression(argument))
+            .collect::<Result<Vec<Type>, Vec<Error>>>()?;
+        let (parameters, returns) = builtin.signature(&argument_types);
+        if parameters.len() != argument_types.len() {
+            return Err(vec![Error::type_error(
+                TypeError::T010 {
+                    expected: parameters.len(),
+                    found: argument_types.len(),
+                },
+                name.clone(),
+            )]);
+        }
+
+        let errors = parameters
+            .into_iter()
+            .zip(argument_types)
+            .enumerate()
+            .filter(|(_, (expected, found))| !expected.accepts(found))
+            .map(|(i, (expected, found))| {
+                // The piped value is the first argument, but written before the pipe
+                let token = match pipe {
+                    Some(pipe) if i == 0 => pipe,
+                    _ => name,
+                };
+                Error::type_error(TypeError::T006 { expected, found }, token.clone())
+            })
+            .collect::<Vec<Error>>();
+
+        if !errors.is_empty() {
+            Err(errors)
+        } else {
+            Ok(returns)
+        }
+    }
+
+    /// Warns if `name` was declared with `@deprecated`
+    fn check_deprecated(&mut self, name: &Token) {
+        let attributes = self.attributes.get(&name.lexeme).cloned();
+        for attribute in attributes.unwrap_or_default() {
+            match attribute {
+                Attribute::Deprecated { message, .. } => self
+                    .warnings
+                    .push(Error::warning(Warning::W001 { message }, name.clone())),
+            }
+        }
+    }
+
+    /// Strings are indexed and sliced by numbers, producing a string
+    fn check_indexing(
+        &self,
+        bracket: &Token,
+        indexed: Type,
+        bounds: Vec<Type>,
+    ) -> Result<Type, Vec<Error>> {
+        let mut errors = Vec::new();
+        if !Type::Text.accepts(&indexed) {
+            errors.push(Error::type_error(
+                TypeError::T006 {
+                    expected: Type::Text,
+                    found: indexed,
+                },
+                bracket.clone(),
+            ));
+        }
+
+        for bound in bounds {
+            if !Type::Number.accepts(&bound) {
+                errors.push(Error::type_error(
+                    TypeError::T006 {
+                        expected: Type::Number,
+                        found: bound,
+                    },
+                    bracket.clone(),
+                ));
+            }
+        }
+
+        if !errors.is_empty() {
+            Err(errors)
+        } else {
+            Ok(Type::Text)
+        }
+    }
+}
+
+/// Whether `ttype` is the type of `{,}`, a set with no known element type
+fn is_empty_set(ttype: &Type) -> bool {
+    matches!(ttype.resolved(), Type::Set(element) if matches!(element.resolved(), Type::Never))
+}
+
+/// `{a}` and `{}` are blocks, which is an easy mistake to make where a set is expected.
+/// Returns an error explaining that if `expr` is such a block and `expected` is a set
+fn block_as_set(expr: &Expr, expected: &Type) -> Option<Error> {
+    match (expr, expected.resolved()) {
+        (
+            Expr::Block {
+                brace, statements, ..
+            },
+            Type::Set(_),
+        ) if statements.is_empty() => Some(Error::type_error(TypeError::T014, brace.clone())),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::types::{Expr, Token, TokenKind, Type};
+    use crate::types::{Attribute, Expr, Pattern, Stmt, Token, TokenKind, Type};
     use pretty_assertions::assert_eq;
+    use std::cell::OnceCell;
     use Expr::*;
     use TokenKind::*;
 
@@ -541,4 +912,457 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn builtin_call() -> Result<(), Vec<Error>> {
+        let callee = Box::new(Variable {
+            name: Token::new(IDENTIFIER("assert".to_string()), 0, 0, "assert"),
+        });
+        let expr_ok = Call {
+            callee: callee.clone(),
+            paren: Token::new(LEFTPAREN, 0, 6, "("),
+            arguments: vec![
+                Literal {
+                    value: Token::new(TRUE, 0, 7, "true"),
+                },
+                Literal {
+                    value: Token::new(STRING("msg".to_string()), 0, 13, "\"msg\""),
+                },
+            ],
+        };
+
+        assert_eq!(
+            Typechecker::new().visit_expression(&expr_ok)?,
+            Type::Tuple(vec![])
+        );
+
+        let expr_bad = Call {
+            callee,
+            paren: Token::new(LEFTPAREN, 0, 6, "("),
+            arguments: vec![Literal {
+                value: Token::new(TRUE, 0, 7, "true"),
+            }],
+        };
+
+        assert_eq!(
+            Typechecker::new().visit_expression(&expr_bad),
+            Err(vec![Error::type_error(
+                TypeError::T010 {
+                    expected: 2,
+                    found: 1
+                },
+                Token::new(IDENTIFIER("assert".to_string()), 0, 0, "assert")
+            )])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn set_membership() -> Result<(), Vec<Error>> {
+        let set = Box::new(Set {
+            brace: Token::new(LEFTBRACE, 0, 5, "{"),
+            elements: vec![
+                Literal {
+                    value: Token::new(NUMBER(1.0), 0, 6, "1"),
+                },
+                Literal {
+                    value: Token::new(NUMBER(2.0), 0, 9, "2"),
+                },
+            ],
+        });
+        let expr_ok = Binary {
+            left: Box::new(Literal {
+                value: Token::new(NUMBER(1.0), 0, 0, "1"),
+            }),
+            operator: Token::new(IN, 0, 2, "in"),
+            right: set.clone(),
+        };
+
+        assert_eq!(Typechecker::new().visit_expression(&expr_ok)?, Type::Bool);
+
+        let expr_bad = Binary {
+            left: Box::new(Literal {
+                value: Token::new(TRUE, 0, 0, "true"),
+            }),
+            operator: Token::new(IN, 0, 5, "in"),
+            right: set,
+        };
+
+        assert_eq!(
+            Typechecker::new().visit_expression(&expr_bad),
+            Err(vec![Error::type_error(
+                TypeError::T001 {
+                    left: Type::Bool,
+                    right: Type::Set(Box::new(Type::Number))
+                },
+                Token::new(IN, 0, 5, "in")
+            )])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tuple_destructuring() -> Result<(), Vec<Error>> {
+        let paren = Token::new(LEFTPAREN, 0, 4, "(");
+        let initializer = Tuple {
+            inners: vec![
+                Literal {
+                    value: Token::new(NUMBER(1.0), 0, 13, "1"),
+                },
+                Literal {
+                    value: Token::new(TRUE, 0, 16, "true"),
+                },
+            ],
+        };
+
+        let stmt_ok = Stmt::Let {
+            attributes: vec![],
+            pattern: Pattern::Tuple {
+                paren: paren.clone(),
+                elements: vec![Pattern::Binding {
+                    name: Token::new(IDENTIFIER("a".to_string()), 0, 5, "a"),
+                }],
+                rest: true,
+            },
+            ttype: None,
+            initializer: initializer.clone(),
+        };
+        let mut typechecker = Typechecker::new();
+        typechecker.check(&vec![stmt_ok])?;
+        assert_eq!(
+            typechecker.visit_expression(&Variable {
+                name: Token::new(IDENTIFIER("a".to_string()), 1, 0, "a"),
+            })?,
+            Type::Number
+        );
+
+        let stmt_bad = Stmt::Let {
+            attributes: vec![],
+            pattern: Pattern::Tuple {
+                paren: paren.clone(),
+                elements: vec![
+                    Pattern::Wildcard {
+                        token: Token::new(IDENTIFIER("_".to_string()), 0, 5, "_"),
+                    },
+                    Pattern::Wildcard {
+                        token: Token::new(IDENTIFIER("_".to_string()), 0, 8, "_"),
+                    },
+                    Pattern::Wildcard {
+                        token: Token::new(IDENTIFIER("_".to_string()), 0, 11, "_"),
+                    },
+                ],
+                rest: false,
+            },
+            ttype: None,
+            initializer,
+        };
+        assert_eq!(
+            Typechecker::new().check(&vec![stmt_bad]),
+            Err(vec![Error::type_error(
+                TypeError::T012 {
+                    found: Type::Tuple(vec![Type::Number, Type::Bool]),
+                    extracted: 3
+                },
+                paren
+            )])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn cast() -> Result<(), Vec<Error>> {
+        let mut typechecker = Typechecker::new();
+        typechecker.check(&vec![Stmt::Let {
+            attributes: vec![],
+            pattern: Pattern::Binding {
+                name: Token::new(IDENTIFIER("x".to_string()), 0, 4, "x"),
+            },
+            ttype: Some(Type::Any),
+            initializer: Literal {
+                value: Token::new(NUMBER(1.0), 0, 13, "1"),
+            },
+        }])?;
+
+        let expr_ok = Cast {
+            expr: Box::new(Variable {
+                name: Token::new(IDENTIFIER("x".to_string()), 1, 0, "x"),
+            }),
+            keyword: Token::new(AS, 1, 2, "as"),
+            ttype: Type::Number,
+            from: OnceCell::new(),
+        };
+        assert_eq!(typechecker.visit_expression(&expr_ok)?, Type::Number);
+        // The interpreter only checks values cast out of `any`
+        match expr_ok {
+            Cast { from, .. } => assert_eq!(from.get(), Some(&Type::Any)),
+            _ => unreachable!(),
+        }
+
+        let expr_bad = Cast {
+            expr: Box::new(Literal {
+                value: Token::new(TRUE, 0, 0, "true"),
+            }),
+            keyword: Token::new(AS, 0, 5, "as"),
+            ttype: Type::Text,
+            from: OnceCell::new(),
+        };
+        assert_eq!(
+            typechecker.visit_expression(&expr_bad),
+            Err(vec![Error::type_error(
+                TypeError::T013 {
+                    from: Type::Bool,
+                    to: Type::Text
+                },
+                Token::new(AS, 0, 5, "as")
+            )])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn deprecated_use_warns() -> Result<(), Vec<Error>> {
+        let mut typechecker = Typechecker::new();
+        typechecker.check(&vec![Stmt::Let {
+            attributes: vec![Attribute::Deprecated {
+                name: Token::new(IDENTIFIER("deprecated".to_string()), 0, 1, "deprecated"),
+                message: None,
+            }],
+            pattern: Pattern::Binding {
+                name: Token::new(IDENTIFIER("x".to_string()), 1, 4, "x"),
+            },
+            ttype: None,
+            initializer: Literal {
+                value: Token::new(NUMBER(1.0), 1, 8, "1"),
+            },
+        }])?;
+        assert_eq!(typechecker.warnings, vec![]);
+
+        let expr = Variable {
+            name: Token::new(IDENTIFIER("x".to_string()), 2, 0, "x"),
+        };
+        assert_eq!(typechecker.visit_expression(&expr)?, Type::Number);
+        assert_eq!(
+            typechecker.warnings,
+            vec![Error::warning(
+                Warning::W001 { message: None },
+                Token::new(IDENTIFIER("x".to_string()), 2, 0, "x")
+            )]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn pipe_passes_first_argument() -> Result<(), Vec<Error>> {
+        let expr = Pipe {
+            pipe: Token::new(PIPEGREATER, 0, 2, "|>"),
+            call: Box::new(Call {
+                callee: Box::new(Variable {
+                    name: Token::new(IDENTIFIER("assert".to_string()), 0, 5, "assert"),
+                }),
+                paren: Token::new(LEFTPAREN, 0, 11, "("),
+                arguments: vec![
+                    Literal {
+                        value: Token::new(NUMBER(1.0), 0, 0, "1"),
+                    },
+                    Literal {
+                        value: Token::new(STRING("msg".to_string()), 0, 12, "\"msg\""),
+                    },
+                ],
+            }),
+        };
+
+        assert_eq!(
+            Typechecker::new().visit_expression(&expr),
+            Err(vec![Error::type_error(
+                TypeError::T006 {
+                    expected: Type::Bool,
+                    found: Type::Number
+                },
+                Token::new(PIPEGREATER, 0, 2, "|>")
+            )])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn block_where_set_expected() -> Result<(), Vec<Error>> {
+        let set = Set {
+            brace: Token::new(LEFTBRACE, 0, 0, "{"),
+            elements: vec![Literal {
+                value: Token::new(NUMBER(1.0), 0, 1, "1"),
+            }],
+        };
+        let block = Block {
+            brace: Token::new(LEFTBRACE, 0, 7, "{"),
+            statements: vec![],
+            finally: Some(Box::new(Literal {
+                value: Token::new(NUMBER(3.0), 0, 8, "3"),
+            })),
+        };
+        let expr = Binary {
+            left: Box::new(set.clone()),
+            operator: Token::new(PIPE, 0, 5, "|"),
+            right: Box::new(block),
+        };
+
+        assert_eq!(
+            Typechecker::new().visit_expression(&expr),
+            Err(vec![Error::type_error(
+                TypeError::T014,
+                Token::new(LEFTBRACE, 0, 7, "{")
+            )])
+        );
+
+        let empty = Set {
+            brace: Token::new(LEFTBRACE, 0, 0, "{"),
+            elements: vec![],
+        };
+        let expr = Binary {
+            left: Box::new(empty),
+            operator: Token::new(PIPE, 0, 4, "|"),
+            right: Box::new(set),
+        };
+        assert_eq!(
+            Typechecker::new().visit_expression(&expr)?,
+            Type::Set(Box::new(Type::Number))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn never_nested_in_tuple() -> Result<(), Vec<Error>> {
+        let keyword = Token::new(IF, 0, 26, "if");
+        let initializer = If {
+            keyword: keyword.clone(),
+            condition: Box::new(Literal {
+                value: Token::new(FALSE, 0, 29, "false"),
+            }),
+            then: Box::new(Tuple {
+                inners: vec![
+                    Call {
+                        callee: Box::new(Variable {
+                            name: Token::new(
+                                IDENTIFIER("unreachable".to_string()),
+                                0,
+                                38,
+                                "unreachable",
+                            ),
+                        }),
+                        paren: Token::new(LEFTPAREN, 0, 49, "("),
+                        arguments: vec![],
+                    },
+                    Literal {
+                        value: Token::new(STRING("a".to_string()), 0, 53, "\"a\""),
+                    },
+                ],
+            }),
+            eelse: Some(Box::new(Tuple {
+                inners: vec![
+                    Literal {
+                        value: Token::new(STRING("b".to_string()), 0, 68, "\"b\""),
+                    },
+                    Literal {
+                        value: Token::new(STRING("c".to_string()), 0, 73, "\"c\""),
+                    },
+                ],
+            })),
+        };
+
+        assert_eq!(
+            Typechecker::new().visit_expression(&initializer)?,
+            Type::Tuple(vec![Type::Text, Type::Text])
+        );
+
+        let name = Token::new(IDENTIFIER("t".to_string()), 0, 4, "t");
+        let stmt = Stmt::Let {
+            attributes: vec![],
+            pattern: Pattern::Binding { name: name.clone() },
+            ttype: Some(Type::Tuple(vec![Type::Number, Type::Text])),
+            initializer,
+        };
+        assert_eq!(
+            Typechecker::new().check(&vec![stmt]),
+            Err(vec![Error::type_error(
+                TypeError::T003 {
+                    declared_as: Type::Tuple(vec![Type::Number, Type::Text]),
+                    initialized_as: Type::Tuple(vec![Type::Text, Type::Text])
+                },
+                name
+            )])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn block_like_statement_must_be_unit() -> Result<(), Vec<Error>> {
+        let keyword = Token::new(IF, 0, 10, "if");
+        let stmt = |then: Expr, eelse: Expr| Stmt::BlockLike {
+            expr: If {
+                keyword: keyword.clone(),
+                condition: Box::new(Literal {
+                    value: Token::new(TRUE, 0, 13, "true"),
+                }),
+                then: Box::new(then),
+                eelse: Some(Box::new(eelse)),
+            },
+        };
+
+        let unit = Tuple { inners: vec![] };
+        Typechecker::new().check(&vec![stmt(unit.clone(), unit)])?;
+
+        let stmt_bad = stmt(
+            Literal {
+                value: Token::new(NUMBER(10.0), 0, 20, "10"),
+            },
+            Literal {
+                value: Token::new(NUMBER(20.0), 0, 32, "20"),
+            },
+        );
+        assert_eq!(
+            Typechecker::new().check(&vec![stmt_bad]),
+            Err(vec![Error::type_error(
+                TypeError::T006 {
+                    expected: Type::Tuple(vec![]),
+                    found: Type::Number
+                },
+                keyword.clone()
+            )])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_binding_in_pattern() -> Result<(), Vec<Error>> {
+        let name = Token::new(IDENTIFIER("a".to_string()), 0, 8, "a");
+        let stmt = Stmt::Let {
+            attributes: vec![],
+            pattern: Pattern::Tuple {
+                paren: Token::new(LEFTPAREN, 0, 4, "("),
+                elements: vec![
+                    Pattern::Binding {
+                        name: Token::new(IDENTIFIER("a".to_string()), 0, 5, "a"),
+                    },
+                    Pattern::Binding { name: name.clone() },
+                ],
+                rest: false,
+            },
+            ttype: None,
+            initializer: Tuple {
+                inners: vec![
+                    Literal {
+                        value: Token::new(NUMBER(1.0), 0, 14, "1"),
+                    },
+                    Literal {
+                        value: Token::new(STRING("x".to_string()), 0, 17, "\"x\""),
+                    },
+                ],
+            },
+        };
+
+        assert_eq!(
+            Typechecker::new().check(&vec![stmt]),
+            Err(vec![Error::type_error(TypeError::T016, name)])
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,11 @@
+use super::Token;
+
+/// Metadata attached to a declaration with `@name` or `@name("argument")`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Attribute {
+    /// Every use of the declared names is reported with a warning
+    Deprecated {
+        name: Token,
+        message: Option<String>,
+    },
+}
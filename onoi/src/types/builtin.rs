@@ -0,0 +1,44 @@
+use super::Type;
+
+/// Functions provided by the language itself
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Builtin {
+    /// `panic(message: string) -> !`
+    Panic,
+    /// `assert(condition: bool, message: string)`
+    Assert,
+    /// `assert_eq(left: T, right: T)`
+    AssertEq,
+    /// `unreachable() -> !`
+    Unreachable,
+}
+
+impl Builtin {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "panic" => Some(Builtin::Panic),
+            "assert" => Some(Builtin::Assert),
+            "assert_eq" => Some(Builtin::AssertEq),
+            "unreachable" => Some(Builtin::Unreachable),
+            _ => None,
+        }
+    }
+
+    /// Parameter types and return type of the builtin when called with `arguments`
+    pub fn signature(&self, arguments: &[Type]) -> (Vec<Type>, Type) {
+        match self {
+            Builtin::Panic => (vec![Type::Text], Type::Never),
+            Builtin::Assert => (vec![Type::Bool, Type::Text], Type::Tuple(Vec::new())),
+            Builtin::AssertEq => {
+                // Compared at their joined type, or else the second must match the first
+                let compared = arguments
+                    .iter()
+                    .try_fold(Type::Never, |joined, argument| joined.join(argument))
+                    .or_else(|| arguments.first().cloned())
+                    .unwrap_or(Type::Never);
+                (vec![compared.clone(), compared], Type::Tuple(Vec::new()))
+            }
+            Builtin::Unreachable => (Vec::new(), Type::Never),
+        }
+    }
+}
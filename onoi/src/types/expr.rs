@@ -1,4 +1,6 @@
-use crate::types::Token;
+use std::cell::OnceCell;
+
+use crate::types::{Token, Type};
 
 use super::Stmt;
 
@@ -11,10 +13,22 @@ pub enum Expr {
     Tuple {
         inners: Vec<Expr>,
     },
+    Set {
+        brace: Token,
+        elements: Vec<Expr>,
+    },
     Unary {
         operator: Token,
         expr: Box<Expr>,
     },
+    Cast {
+        expr: Box<Expr>,
+        keyword: Token,
+        ttype: Type,
+        /// Static type of `expr`, recorded by the typechecker. Tells the interpreter
+        /// whether to convert the value or only check a value coming out of `any`
+        from: OnceCell<Type>,
+    },
     Binary {
         operator: Token,
         left: Box<Expr>,
@@ -31,11 +45,33 @@ pub enum Expr {
     Variable {
         name: Token,
     },
+    Call {
+        callee: Box<Expr>,
+        paren: Token,
+        arguments: Vec<Expr>,
+    },
+    /// `left |> f(..)`, holding the Expr::Call `f(left, ..)` it stands for
+    Pipe {
+        pipe: Token,
+        call: Box<Expr>,
+    },
+    Index {
+        expr: Box<Expr>,
+        bracket: Token,
+        index: Box<Expr>,
+    },
+    Slice {
+        expr: Box<Expr>,
+        bracket: Token,
+        start: Option<Box<Expr>>,
+        end: Option<Box<Expr>>,
+    },
     Assign {
         name: Token,
         expr: Box<Expr>
     },
     Block {
+        brace: Token,
         statements: Vec<Stmt>,
         finally: Option<Box<Expr>>
     },
@@ -52,3 +88,12 @@ pub enum Expr {
     }
 }
 
+impl Expr {
+    /// Whether this expression ends in a block, so it can be a statement without a ';'
+    pub fn is_block_like(&self) -> bool {
+        matches!(
+            self,
+            Expr::If { .. } | Expr::While { .. } | Expr::Block { .. }
+        )
+    }
+}
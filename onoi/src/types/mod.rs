@@ -1,9 +1,15 @@
+pub mod attribute;
+pub mod builtin;
 pub mod expr;
+pub mod pattern;
 pub mod stmt;
 pub mod token;
 pub mod value;
 
+pub use attribute::*;
+pub use builtin::*;
 pub use expr::*;
+pub use pattern::*;
 pub use stmt::*;
 pub use token::*;
 pub use value::*;
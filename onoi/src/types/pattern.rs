@@ -0,0 +1,45 @@
+use super::Token;
+
+/// Describes the shape of a value and the names its parts are bound to
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Binding {
+        name: Token,
+    },
+    /// `_` matches anything without binding it
+    Wildcard {
+        token: Token,
+    },
+    Literal {
+        value: Token,
+    },
+    /// `(a, b, ...)` where `rest` marks a trailing `...` that ignores remaining elements
+    Tuple {
+        paren: Token,
+        elements: Vec<Pattern>,
+        rest: bool,
+    },
+}
+
+impl Pattern {
+    /// The token errors about this pattern are reported at
+    pub fn token(&self) -> &Token {
+        match self {
+            Pattern::Binding { name } => name,
+            Pattern::Wildcard { token } => token,
+            Pattern::Literal { value } => value,
+            Pattern::Tuple { paren, .. } => paren,
+        }
+    }
+
+    /// The names this pattern binds, from left to right
+    pub fn bindings(&self) -> Vec<&Token> {
+        match self {
+            Pattern::Binding { name } => vec![name],
+            Pattern::Wildcard { .. } | Pattern::Literal { .. } => Vec::new(),
+            Pattern::Tuple { elements, .. } => {
+                elements.iter().flat_map(Pattern::bindings).collect()
+            }
+        }
+    }
+}
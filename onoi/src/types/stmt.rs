@@ -1,9 +1,18 @@
-use super::{Expr, Type, Token};
+use super::{Attribute, Expr, Pattern, Type, Token};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Stmt {
     Expression { expr: Expr },
-    Let { name: Token, ttype: Option<Type>, initializer: Expr }
+    /// An if, while or block without a ';' that is followed by more statements.
+    /// Its value is not dropped like with a ';', so it has to be `()`
+    BlockLike { expr: Expr },
+    Let {
+        attributes: Vec<Attribute>,
+        pattern: Pattern,
+        ttype: Option<Type>,
+        initializer: Expr,
+    },
+    Type { name: Token, ttype: Type },
 }
 
 
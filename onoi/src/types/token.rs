@@ -10,18 +10,24 @@ pub enum TokenKind {
     RIGHTBRACE,
 
     COMMA,
+    DOTDOT,
+    DOTDOTDOT,
     EQUAL,
     COLON,
     SEMICOLON,
+    AT,
 
     // Keywords
     LET,
+    TYPE,
     IF,
     ELSE,
     WHILE,
     STRINGKW,
     NUMBERKW,
     BOOL,
+    ANY,
+    AS,
 
     // Math operators
     MINUS,
@@ -40,6 +46,14 @@ pub enum TokenKind {
     AND,
     OR,
 
+    // Set operators
+    PIPE,
+    AMPERSAND,
+    IN,
+
+    // Pipeline operator
+    PIPEGREATER,
+
     // Literals
     STRING(String),
     NUMBER(f64),
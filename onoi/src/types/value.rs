@@ -2,12 +2,124 @@ use super::{Token, TokenKind};
 use std::fmt::{Debug, Display};
 
 /// The types ono supports
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum Type {
     Text,
     Number,
     Bool,
+    /// Holds a value of any type. Getting it back out requires a cast checked at runtime
+    Any,
     Tuple(Vec<Type>),
+    Set(Box<Type>),
+    /// A named alias for another type, introduced by a `type` declaration
+    Alias(String, Box<Type>),
+    /// A type parameter of a generic alias. Only lives inside alias definitions
+    Param(String),
+    /// The type of expressions that never produce a value, like `panic(..)`
+    Never,
+}
+
+impl Type {
+    /// Strips away any aliases, returning the type they refer to
+    pub fn resolved(&self) -> &Type {
+        match self {
+            Type::Alias(_, ttype) => ttype.resolved(),
+            ttype => ttype,
+        }
+    }
+
+    /// Whether a value of type `other` can be stored where `self` is expected
+    pub fn accepts(&self, other: &Type) -> bool {
+        match (self.resolved(), other.resolved()) {
+            (Type::Any, _) => true,
+            // A diverging expression can stand in for any value
+            (_, Type::Never) => true,
+            (Type::Tuple(left), Type::Tuple(right)) => {
+                left.len() == right.len()
+                    && left
+                        .iter()
+                        .zip(right)
+                        .all(|(left, right)| left.accepts(right))
+            }
+            (Type::Set(left), Type::Set(right)) => left.accepts(right),
+            (left, right) => left == right,
+        }
+    }
+
+    /// The type of a value that is either of type `self` or of type `other`, like the
+    /// value of an `if` with one branch of each. `Never` joins with every type.
+    /// Returns `None` if there is no such type
+    pub fn join(&self, other: &Type) -> Option<Type> {
+        if self == other {
+            return Some(self.clone());
+        }
+        match (self.resolved(), other.resolved()) {
+            (Type::Never, _) => Some(other.clone()),
+            (_, Type::Never) => Some(self.clone()),
+            (Type::Tuple(left), Type::Tuple(right)) if left.len() == right.len() => left
+                .iter()
+                .zip(right)
+                .map(|(left, right)| left.join(right))
+                .collect::<Option<Vec<Type>>>()
+                .map(Type::Tuple),
+            (Type::Set(left), Type::Set(right)) => {
+                left.join(right).map(|inner| Type::Set(Box::new(inner)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether `self as to` is allowed. Casts out of `any` are checked at runtime
+    pub fn casts_to(&self, to: &Type) -> bool {
+        match (self.resolved(), to.resolved()) {
+            (Type::Any, _) => true,
+            (Type::Number, Type::Text) | (Type::Bool, Type::Number) => true,
+            (Type::Tuple(from), Type::Tuple(to)) => {
+                from.len() == to.len() && from.iter().zip(to).all(|(from, to)| from.casts_to(to))
+            }
+            (Type::Set(from), Type::Set(to)) => from.casts_to(to),
+            _ => to.accepts(self),
+        }
+    }
+
+    /// Replaces type parameters with the types bound to them
+    pub fn substitute(&self, bindings: &[(String, Type)]) -> Type {
+        match self {
+            Type::Param(name) => bindings
+                .iter()
+                .find(|(param, _)| param == name)
+                .map(|(_, ttype)| ttype.clone())
+                .unwrap_or_else(|| self.clone()),
+            Type::Tuple(inners) => Type::Tuple(
+                inners
+                    .iter()
+                    .map(|inner| inner.substitute(bindings))
+                    .collect(),
+            ),
+            Type::Set(inner) => Type::Set(Box::new(inner.substitute(bindings))),
+            Type::Alias(name, ttype) => {
+                Type::Alias(name.clone(), Box::new(ttype.substitute(bindings)))
+            }
+            ttype => ttype.clone(),
+        }
+    }
+}
+
+/// Aliases are transparent, so two types are equal if they are equal once resolved
+impl PartialEq for Type {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.resolved(), other.resolved()) {
+            (Type::Text, Type::Text)
+            | (Type::Number, Type::Number)
+            | (Type::Bool, Type::Bool)
+            | (Type::Any, Type::Any)
+            | (Type::Never, Type::Never) => true,
+            (Type::Tuple(left), Type::Tuple(right)) => left == right,
+            (Type::Set(left), Type::Set(right)) => left == right,
+            (Type::Param(left), Type::Param(right)) => left == right,
+            _ => false,
+        }
+    }
 }
 
 impl From<&Token> for Type {
@@ -27,6 +139,7 @@ impl Display for Type {
             Type::Bool => write!(f, "bool"),
             Type::Text => write!(f, "string"),
             Type::Number => write!(f, "number"),
+            Type::Any => write!(f, "any"),
             Type::Tuple(inners) => write!(
                 f,
                 "({})",
@@ -36,30 +149,132 @@ impl Display for Type {
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
+            // The empty set, written the same as its literal
+            Type::Set(inner) if matches!(inner.resolved(), Type::Never) => write!(f, "{{,}}"),
+            Type::Set(inner) => write!(f, "{{{}}}", inner),
+            Type::Alias(name, _) => write!(f, "{}", name),
+            Type::Param(name) => write!(f, "{}", name),
+            Type::Never => write!(f, "!"),
         }
     }
 }
 
 /// Representation of a value
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum Value {
     Bool(bool),
     Text(String),
     Number(f64),
-    Tuple(Vec<Value>)
+    Tuple(Vec<Value>),
+    /// Elements are unique and kept in insertion order
+    Set(Vec<Value>),
 }
 
 impl Value {
-    /// null and `false` are falsy in ono. Everything else is thruthy.
+    /// Builds a set from `elements`, dropping duplicates
+    pub fn set(elements: Vec<Value>) -> Self {
+        let mut unique: Vec<Value> = Vec::with_capacity(elements.len());
+        for element in elements {
+            if !unique.contains(&element) {
+                unique.push(element);
+            }
+        }
+        Value::Set(unique)
+    }
+
+    /// Converts this value of static type `from` to `to` as done by `value as to`.
+    /// Values coming out of `any` are only checked, never converted.
+    /// Returns `None` if the value does not have a conversion to `to`
+    pub fn cast(self, from: &Type, to: &Type) -> Option<Value> {
+        match (self, from.resolved(), to.resolved()) {
+            (value, Type::Any, to) => value.is_of_type(to).then_some(value),
+            (Value::Number(number), Type::Number, Type::Text) => {
+                Some(Value::Text(number.to_string()))
+            }
+            (Value::Bool(boolean), Type::Bool, Type::Number) => {
+                Some(Value::Number(if boolean { 1.0 } else { 0.0 }))
+            }
+            (Value::Tuple(values), Type::Tuple(froms), Type::Tuple(tos))
+                if values.len() == froms.len() && values.len() == tos.len() =>
+            {
+                values
+                    .into_iter()
+                    .zip(froms.iter().zip(tos))
+                    .map(|(value, (from, to))| value.cast(from, to))
+                    .collect::<Option<Vec<Value>>>()
+                    .map(Value::Tuple)
+            }
+            (Value::Set(elements), Type::Set(from), Type::Set(to)) => elements
+                .into_iter()
+                .map(|element| element.cast(from, to))
+                .collect::<Option<Vec<Value>>>()
+                .map(Value::set),
+            (value, _, to) if value.is_of_type(to) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Whether this value can be seen as a value of type `ttype`
+    pub fn is_of_type(&self, ttype: &Type) -> bool {
+        match (self, ttype.resolved()) {
+            (_, Type::Any) => true,
+            (Value::Bool(_), Type::Bool)
+            | (Value::Text(_), Type::Text)
+            | (Value::Number(_), Type::Number) => true,
+            (Value::Tuple(values), Type::Tuple(types)) => {
+                values.len() == types.len()
+                    && values
+                        .iter()
+                        .zip(types)
+                        .all(|(value, ttype)| value.is_of_type(ttype))
+            }
+            (Value::Set(elements), Type::Set(ttype)) => {
+                elements.iter().all(|element| element.is_of_type(ttype))
+            }
+            _ => false,
+        }
+    }
+
+    /// The type of this value as seen at runtime
+    pub fn type_of(&self) -> Type {
+        match self {
+            Value::Bool(_) => Type::Bool,
+            Value::Text(_) => Type::Text,
+            Value::Number(_) => Type::Number,
+            Value::Tuple(values) => Type::Tuple(values.iter().map(Value::type_of).collect()),
+            Value::Set(elements) => Type::Set(Box::new(
+                elements.first().map(Value::type_of).unwrap_or(Type::Any),
+            )),
+        }
+    }
+
+    /// null, `false` and empty sets are falsy in ono. Everything else is thruthy.
     pub fn is_truthy(&self) -> bool {
         match self {
             Value::Tuple(inner) => inner.len() == 0,
+            Value::Set(elements) => !elements.is_empty(),
             Value::Bool(val) => *val,
             _ => true,
         }
     }
 }
 
+/// Sets are equal when they hold the same elements, regardless of order
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Bool(left), Value::Bool(right)) => left == right,
+            (Value::Text(left), Value::Text(right)) => left == right,
+            (Value::Number(left), Value::Number(right)) => left == right,
+            (Value::Tuple(left), Value::Tuple(right)) => left == right,
+            (Value::Set(left), Value::Set(right)) => {
+                left.len() == right.len() && left.iter().all(|element| right.contains(element))
+            }
+            _ => false,
+        }
+    }
+}
+
 impl From<&Token> for Value {
     fn from(token: &Token) -> Self {
         match &token.kind {
@@ -87,6 +302,15 @@ impl Display for Value {
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
+            Value::Set(elements) => write!(
+                f,
+                "{{{}}}",
+                elements
+                    .iter()
+                    .map(|element| format!("{}", element))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
         }
     }
 }
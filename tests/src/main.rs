@@ -54,25 +54,27 @@ fn run_test(path: &Path) -> Result<(), String> {
         _ => panic!("{:?} must contain --OUT-- section", path)
     };
 
-    let result = match onoi::run(code) {
-        Ok(_val) => Ok(()), // TODO: Match val with _out
-        Err(mut errors) => {
-            let filename = path.to_str().unwrap();
-            let lines = code.split('\n').collect::<Vec<_>>();
-            let error_str = errors.iter_mut().map(|err| {
-                let line = lines.get(err.token.position.line).expect("src line refered by error should be in src code");
-                err.with_filename(filename);
-                err.with_src_line(line);
-                format!("{}", err)
-            }).collect::<Vec<_>>().join("\n");
-            
-            if error_str != err {
-                let comparison = StrComparison::new(&error_str, err);
-                Err(comparison.to_string())
-            } else {
-                Ok(())
-            }
-        }
+    // Warnings are expected in the --ERR-- section before any errors
+    let mut diagnostics = Vec::new();
+    let result = onoi::run(code, &mut diagnostics);
+    if let Err(mut errors) = result { // TODO: Match Ok value with _out
+        diagnostics.append(&mut errors);
+    }
+
+    let filename = path.to_str().unwrap();
+    let lines = code.split('\n').collect::<Vec<_>>();
+    let error_str = diagnostics.iter_mut().map(|err| {
+        let line = lines.get(err.token.position.line).expect("src line refered by error should be in src code");
+        err.with_filename(filename);
+        err.with_src_line(line);
+        format!("{}", err)
+    }).collect::<Vec<_>>().join("\n");
+
+    let result = if error_str != err {
+        let comparison = StrComparison::new(&error_str, err);
+        Err(comparison.to_string())
+    } else {
+        Ok(())
     };
 
     // undo override of colored output